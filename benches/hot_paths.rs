@@ -0,0 +1,55 @@
+//! Timing of the per-element operations, run with `cargo bench`. Host timings only show
+//! relative cost; for absolute numbers, count cycles on the target.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use bare_metal_deque::BareMetalDeque;
+
+const ROUNDS: usize = 100_000;
+
+fn time(name: &str, mut f: impl FnMut()) {
+    let started = Instant::now();
+    for _ in 0..ROUNDS {
+        f();
+    }
+    println!("{name:<24} {:>8.2} ns/round", started.elapsed().as_nanos() as f64 / ROUNDS as f64);
+}
+
+fn main() {
+    let mut q = BareMetalDeque::<u32, 61>::new();
+    time("push_back/pop_front", || {
+        for x in 0..40 {
+            q.push_back(black_box(x));
+        }
+        for _ in 0..40 {
+            black_box(q.pop_front());
+        }
+    });
+
+    let mut q = BareMetalDeque::<u32, 61>::new();
+    time("push_front/pop_back", || {
+        for x in 0..40 {
+            q.push_front(black_box(x));
+        }
+        for _ in 0..40 {
+            black_box(q.pop_back());
+        }
+    });
+
+    let mut q = BareMetalDeque::<u32, 61>::new();
+    for x in 0..30 {
+        q.push_back(x);
+        q.pop_front();
+    }
+    for x in 0..61 {
+        q.push_back(x);
+    }
+    time("index", || {
+        let mut sum = 0u32;
+        for i in 0..61 {
+            sum = sum.wrapping_add(q[black_box(i)]);
+        }
+        black_box(sum);
+    });
+}
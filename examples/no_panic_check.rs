@@ -0,0 +1,30 @@
+//! Uses every method marked with `no_panic`. With the `no-panic` feature this links only
+//! if none of them can panic, so build it to check panic-freedom:
+//!
+//! ```text
+//! cargo build --release --features no-panic --example no_panic_check
+//! ```
+
+use std::hint::black_box;
+
+use bare_metal_deque::BareMetalDeque;
+
+fn main() {
+    check::<7>();
+    check::<0>();
+}
+
+fn check<const N: usize>() {
+    let mut q = black_box(BareMetalDeque::<u32, N>::new());
+    let _ = black_box(q.try_push_back(black_box(1)));
+    let _ = black_box(q.try_push_front(black_box(2)));
+    black_box(q.get(black_box(1)));
+    if let Some(x) = q.get_mut(black_box(0)) {
+        *x += 1;
+    }
+    black_box(q.front());
+    black_box(q.back());
+    black_box(q.remove(black_box(1)));
+    black_box(q.pop_front());
+    black_box(q.pop_back());
+}
@@ -0,0 +1,84 @@
+//! Over-aligned storage, for deques that a DMA engine reads or writes directly or that
+//! must sit on their own cache line.
+//!
+//! ```
+//! use bare_metal_deque::{A32, Aligned, ByteDeque};
+//!
+//! let mut tx: Aligned<A32, ByteDeque<64>> = Aligned::new(ByteDeque::new_with(0));
+//! tx.write(b"hello");
+//! let (front, _) = tx.as_slices();
+//! assert_eq!(front.as_ptr() as usize % 32, 0);
+//! ```
+
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+
+/// Wraps `T`, raising its alignment to that of the marker type `A`, such as [`A32`].
+///
+/// A `BareMetalDeque` keeps its backing array at offset zero, so an aligned deque's array
+/// starts on an `A` boundary. Elements are only at that boundary when the deque is empty
+/// or its contents start at the beginning of the ring.
+#[derive(Copy, Clone, Default)]
+#[repr(C)]
+pub struct Aligned<A, T> {
+    _alignment: [A; 0],
+    value: T,
+}
+
+impl<A, T> Aligned<A, T> {
+    pub const fn new(value: T) -> Self {
+        Self { _alignment: [], value }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<A, T> Deref for Aligned<A, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<A, T> DerefMut for Aligned<A, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<A, T: fmt::Debug> fmt::Debug for Aligned<A, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+macro_rules! alignment_markers {
+    ($($name:ident = $align:literal),*) => {
+        $(
+            #[doc = concat!("Marker for ", stringify!($align), "-byte alignment.")]
+            #[derive(Copy, Clone, Debug, Default)]
+            #[repr(align($align))]
+            pub struct $name;
+        )*
+    };
+}
+
+alignment_markers!(A4 = 4, A8 = 8, A16 = 16, A32 = 32, A64 = 64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BareMetalDeque;
+
+    #[test]
+    fn alignment_test() {
+        let queues = [Aligned::<A64, BareMetalDeque<u8, 3>>::default(); 3];
+        for q in &queues {
+            assert_eq!(q.as_slices().0.as_ptr() as usize % 64, 0);
+        }
+        assert_eq!(core::mem::align_of_val(&queues[0]), 64);
+    }
+}
@@ -0,0 +1,75 @@
+//! Conversions between `BareMetalDeque` and the `alloc` collections, behind the `alloc`
+//! feature, so host-side tools and tests can share data with firmware code. Converting
+//! into a `Vec` or `VecDeque` always succeeds; converting back fails with
+//! [`CapacityError`] if the contents do not fit.
+//!
+//! ```
+//! use std::collections::VecDeque;
+//! use bare_metal_deque::BareMetalDeque;
+//!
+//! let mut q = BareMetalDeque::<u8, 4>::new();
+//! q.push_back(1);
+//! q.push_front(0);
+//!
+//! let v = VecDeque::from(q);
+//! assert_eq!(v, [0, 1]);
+//! let back = BareMetalDeque::<u8, 2>::try_from(v).unwrap();
+//! assert_eq!(Vec::from(back), vec![0, 1]);
+//! assert!(BareMetalDeque::<u8, 1>::try_from(vec![1, 2]).is_err());
+//! ```
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use crate::{BareMetalDeque, CapacityError};
+
+impl<T: Copy + Default, const N: usize> From<BareMetalDeque<T, N>> for VecDeque<T> {
+    fn from(deque: BareMetalDeque<T, N>) -> Self {
+        deque.iter().copied().collect()
+    }
+}
+
+impl<T: Copy + Default, const N: usize> From<BareMetalDeque<T, N>> for Vec<T> {
+    fn from(deque: BareMetalDeque<T, N>) -> Self {
+        deque.iter().copied().collect()
+    }
+}
+
+impl<T: Copy + Default, const N: usize> TryFrom<VecDeque<T>> for BareMetalDeque<T, N> {
+    type Error = CapacityError;
+
+    fn try_from(deque: VecDeque<T>) -> Result<Self, CapacityError> {
+        Self::try_from_exact(deque.iter().copied())
+    }
+}
+
+impl<T: Copy + Default, const N: usize> TryFrom<Vec<T>> for BareMetalDeque<T, N> {
+    type Error = CapacityError;
+
+    fn try_from(vec: Vec<T>) -> Result<Self, CapacityError> {
+        Self::try_from_exact(vec.iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapped_conversion_test() {
+        let mut q = BareMetalDeque::<i32, 3>::new();
+        for x in [1, 2, 3] {
+            q.push_back(x);
+        }
+        q.pop_front();
+        q.push_back(4);
+        assert_eq!(VecDeque::from(q), [2, 3, 4]);
+        assert_eq!(Vec::from(q), [2, 3, 4]);
+
+        let mut v = VecDeque::from(q);
+        v.push_front(1);
+        assert_eq!(BareMetalDeque::<i32, 3>::try_from(v.clone()).unwrap_err(), CapacityError);
+        let back = BareMetalDeque::<i32, 4>::try_from(v).unwrap();
+        assert_eq!(Vec::from(back), [1, 2, 3, 4]);
+    }
+}
@@ -0,0 +1,42 @@
+//! [`Arbitrary`] support behind the `arbitrary` feature, so fuzz targets can take
+//! `BareMetalDeque` values as input. Generated deques vary in length and in where their
+//! contents start within the ring, so wrapped layouts are exercised too.
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::BareMetalDeque;
+
+impl<'a, T, const MAX_STORED: usize> Arbitrary<'a> for BareMetalDeque<T, MAX_STORED>
+where
+    T: Arbitrary<'a> + Copy + Clone + Default,
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut result = Self::new();
+        if MAX_STORED == 0 {
+            return Ok(result);
+        }
+        result.start = u.choose_index(MAX_STORED)?;
+        let len = u.int_in_range(0..=MAX_STORED)?;
+        for _ in 0..len {
+            result.push_back(T::arbitrary(u)?);
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapped_arbitrary_test() {
+        let data = [3, 3, 10, 20, 30];
+        let q = BareMetalDeque::<u8, 4>::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        assert_eq!(q.len(), 3);
+        assert_eq!(q.start, 3);
+        assert_eq!(q.as_slices(), (&[10][..], &[20, 30][..]));
+
+        let empty = BareMetalDeque::<u8, 0>::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        assert!(empty.is_empty());
+    }
+}
@@ -0,0 +1,175 @@
+//! A heap-allocated deque, behind the `alloc` feature, for capacities too large to build
+//! on the stack.
+//!
+//! `BareMetalDeque::<u8, 16384>::new()` creates its array on the stack before moving it
+//! anywhere, even into a `Box`, which can overflow a small MCU stack or a test thread.
+//! [`BoxedDeque`] allocates its storage directly on the heap instead. It offers the core
+//! queue operations, and [`BoxedDeque::view`] gives read access through a [`DequeView`].
+//!
+//! ```
+//! use bare_metal_deque::BoxedDeque;
+//!
+//! let mut log = BoxedDeque::<u8, 1_000_000>::new();
+//! log.push_back(b'a');
+//! log.push_front(b'z');
+//! assert_eq!(log.view(), *b"za");
+//! assert_eq!(log.pop_back(), Some(b'a'));
+//! ```
+
+use alloc::boxed::Box;
+use alloc::vec;
+
+use crate::{CapacityError, DequeView};
+
+#[derive(Clone, Debug)]
+pub struct BoxedDeque<T, const MAX_STORED: usize> {
+    array: Box<[T; MAX_STORED]>,
+    start: usize,
+    size: usize,
+}
+
+impl<T: Copy + Default, const MAX_STORED: usize> Default for BoxedDeque<T, MAX_STORED> {
+    fn default() -> Self {
+        Self::new_with(T::default())
+    }
+}
+
+impl<T: Copy + Default, const MAX_STORED: usize> BoxedDeque<T, MAX_STORED> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty deque with its unused storage set to `fill`.
+    pub fn new_with(fill: T) -> Self {
+        let Ok(array) = vec![fill; MAX_STORED].into_boxed_slice().try_into() else {
+            unreachable!("the vector has exactly MAX_STORED elements");
+        };
+        Self { array, start: 0, size: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.size == MAX_STORED
+    }
+
+    pub fn clear(&mut self) {
+        self.start = 0;
+        self.size = 0;
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        if self.try_push_front(value).is_err() {
+            panic!("Deque is full");
+        }
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        if self.try_push_back(value).is_err() {
+            panic!("Deque is full");
+        }
+    }
+
+    pub fn try_push_front(&mut self, value: T) -> Result<(), CapacityError> {
+        if self.is_full() {
+            return Err(CapacityError);
+        }
+        self.start = self.physical(MAX_STORED - 1);
+        self.array[self.start] = value;
+        self.size += 1;
+        Ok(())
+    }
+
+    pub fn try_push_back(&mut self, value: T) -> Result<(), CapacityError> {
+        if self.is_full() {
+            return Err(CapacityError);
+        }
+        let index = self.physical(self.size);
+        self.array[index] = value;
+        self.size += 1;
+        Ok(())
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let result = self.front();
+        if result.is_some() {
+            self.start = self.physical(1);
+            self.size -= 1;
+        }
+        result
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        let result = self.back();
+        if result.is_some() {
+            self.size -= 1;
+        }
+        result
+    }
+
+    pub fn front(&self) -> Option<T> {
+        self.get(0).copied()
+    }
+
+    pub fn back(&self) -> Option<T> {
+        self.get(self.size.checked_sub(1)?).copied()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        (index < self.size).then(|| &self.array[self.physical(index)])
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if index < self.size {
+            let index = self.physical(index);
+            Some(&mut self.array[index])
+        } else {
+            None
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let (front, back) = self.view().as_slices();
+        front.iter().chain(back)
+    }
+
+    /// The contents as a view, for copying out, splitting, or comparing.
+    pub fn view(&self) -> DequeView<'_, T> {
+        let first_len = self.size.min(MAX_STORED - self.start);
+        DequeView::new((&self.array[self.start..self.start + first_len], &self.array[..self.size - first_len]))
+    }
+
+    /// Maps an offset of less than `MAX_STORED` from the front to an array index.
+    fn physical(&self, offset: usize) -> usize {
+        let index = self.start + offset;
+        if index < MAX_STORED { index } else { index - MAX_STORED }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn large_boxed_test() {
+        let mut q = BoxedDeque::<u32, 65536>::new();
+        for x in 0..65536 {
+            q.push_front(x);
+        }
+        assert_eq!(q.try_push_back(0), Err(CapacityError));
+        assert_eq!(q.pop_back(), Some(0));
+        q.push_back(7);
+        *q.get_mut(0).unwrap() += 1;
+        assert_eq!((q.front(), q.back(), q.get(65536)), (Some(65536), Some(7), None));
+        assert_eq!(q.iter().filter(|x| **x == 7).count(), 2);
+        q.clear();
+        assert!(q.is_empty());
+        assert!(BoxedDeque::<u8, 0>::new().try_push_front(1).is_err());
+    }
+}
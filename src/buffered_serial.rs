@@ -0,0 +1,205 @@
+//! Interrupt-driven buffering for an [`embedded-hal-nb`](https://docs.rs/embedded-hal-nb)
+//! serial port, behind the `embedded-hal-nb` feature.
+//!
+//! The receive interrupt calls [`BufferedSerial::on_rx`] to move received bytes into
+//! the RX ring, and the transmit-empty interrupt calls [`BufferedSerial::on_tx_empty`]
+//! to feed the port from the TX ring. Application code uses `read()` and `write()`,
+//! which never block. Sharing the adapter between interrupt and application code (for
+//! example in a `critical_section::Mutex`) is up to the caller.
+//!
+//! ```
+//! use bare_metal_deque::BufferedSerial;
+//! use embedded_hal_nb::nb;
+//! use embedded_hal_nb::serial::{ErrorKind, ErrorType, Read, Write};
+//!
+//! /// A loopback port with a one-byte data register.
+//! struct Loopback(Option<u8>);
+//!
+//! impl ErrorType for Loopback {
+//!     type Error = ErrorKind;
+//! }
+//!
+//! impl Read for Loopback {
+//!     fn read(&mut self) -> nb::Result<u8, ErrorKind> {
+//!         self.0.take().ok_or(nb::Error::WouldBlock)
+//!     }
+//! }
+//!
+//! impl Write for Loopback {
+//!     fn write(&mut self, b: u8) -> nb::Result<(), ErrorKind> {
+//!         if self.0.is_some() {
+//!             return Err(nb::Error::WouldBlock);
+//!         }
+//!         self.0 = Some(b);
+//!         Ok(())
+//!     }
+//!
+//!     fn flush(&mut self) -> nb::Result<(), ErrorKind> {
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let mut serial = BufferedSerial::<_, 8, 8>::new(Loopback(None));
+//! assert_eq!(serial.write(b"hi"), 2);
+//! let mut buf = [0; 8];
+//! for _ in 0..2 {
+//!     serial.on_rx();       // receive interrupt
+//!     serial.on_tx_empty(); // transmit-empty interrupt
+//! }
+//! serial.on_rx();
+//! assert_eq!(serial.read(&mut buf), 2);
+//! assert_eq!(&buf[..2], b"hi");
+//! ```
+
+use embedded_hal_nb::nb;
+use embedded_hal_nb::serial::{Read, Write};
+
+use crate::ByteDeque;
+
+pub struct BufferedSerial<S, const TX: usize, const RX: usize> {
+    serial: S,
+    tx: ByteDeque<TX>,
+    rx: ByteDeque<RX>,
+    overruns: u32,
+    errors: u32,
+}
+
+impl<S: Read + Write, const TX: usize, const RX: usize> BufferedSerial<S, TX, RX> {
+    pub fn new(serial: S) -> Self {
+        Self { serial, tx: ByteDeque::new(), rx: ByteDeque::new(), overruns: 0, errors: 0 }
+    }
+
+    pub fn release(self) -> S {
+        self.serial
+    }
+
+    /// Queues as much of `data` as fits for transmission and starts sending it, returning
+    /// the number of bytes queued.
+    pub fn write(&mut self, data: &[u8]) -> usize {
+        let n = self.tx.write(data);
+        self.on_tx_empty();
+        n
+    }
+
+    /// Removes received bytes into `buf`, returning the number of bytes read.
+    pub fn read(&mut self, buf: &mut [u8]) -> usize {
+        self.rx.read(buf)
+    }
+
+    pub fn tx_pending(&self) -> usize {
+        self.tx.len()
+    }
+
+    pub fn rx_available(&self) -> usize {
+        self.rx.len()
+    }
+
+    /// Bytes received while the RX ring was full, and therefore dropped.
+    pub fn overruns(&self) -> u32 {
+        self.overruns
+    }
+
+    /// Errors reported by the underlying port.
+    pub fn errors(&self) -> u32 {
+        self.errors
+    }
+
+    /// Moves every byte the port has ready into the RX ring, stopping at the first error
+    /// so that a port with a sticky error flag cannot hold the interrupt forever. Call
+    /// from the receive interrupt.
+    pub fn on_rx(&mut self) {
+        loop {
+            match self.serial.read() {
+                Ok(_) if self.rx.is_full() => self.overruns = self.overruns.wrapping_add(1),
+                Ok(b) => self.rx.push_back(b),
+                Err(nb::Error::WouldBlock) => break,
+                Err(nb::Error::Other(_)) => {
+                    self.errors = self.errors.wrapping_add(1);
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Feeds queued bytes to the port until it stops accepting them. Call from the
+    /// transmit-empty interrupt.
+    pub fn on_tx_empty(&mut self) {
+        while let Some(b) = self.tx.front() {
+            match self.serial.write(b) {
+                Ok(()) => {
+                    self.tx.pop_front();
+                }
+                Err(nb::Error::WouldBlock) => break,
+                Err(nb::Error::Other(_)) => {
+                    self.errors = self.errors.wrapping_add(1);
+                    break;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal_nb::serial::{ErrorKind, ErrorType};
+
+    /// A port whose receive FIFO is preloaded and whose transmit FIFO holds four bytes.
+    struct FakePort {
+        incoming: ByteDeque<16>,
+        sent: ByteDeque<4>,
+    }
+
+    impl ErrorType for FakePort {
+        type Error = ErrorKind;
+    }
+
+    impl Read for FakePort {
+        fn read(&mut self) -> nb::Result<u8, ErrorKind> {
+            match self.incoming.pop_front() {
+                Some(0xFF) => Err(nb::Error::Other(ErrorKind::Parity)),
+                Some(b) => Ok(b),
+                None => Err(nb::Error::WouldBlock),
+            }
+        }
+    }
+
+    impl Write for FakePort {
+        fn write(&mut self, b: u8) -> nb::Result<(), ErrorKind> {
+            if self.sent.is_full() {
+                return Err(nb::Error::WouldBlock);
+            }
+            self.sent.push_back(b);
+            Ok(())
+        }
+
+        fn flush(&mut self) -> nb::Result<(), ErrorKind> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn buffering_test() {
+        let mut incoming = ByteDeque::new();
+        incoming.write(&[1, 2, 0xFF, 3, 4, 5]);
+        let mut serial = BufferedSerial::<_, 8, 4>::new(FakePort { incoming, sent: ByteDeque::new() });
+
+        serial.on_rx();
+        assert_eq!(serial.rx_available(), 2);
+        assert_eq!(serial.errors(), 1);
+        serial.on_rx();
+        assert_eq!(serial.rx_available(), 4);
+        assert_eq!((serial.overruns(), serial.errors()), (1, 1));
+        let mut buf = [0; 8];
+        assert_eq!(serial.read(&mut buf), 4);
+        assert_eq!(&buf[..4], &[1, 2, 3, 4]);
+
+        assert_eq!(serial.write(b"0123456789"), 8);
+        assert_eq!(serial.tx_pending(), 4);
+        serial.serial.sent.read(&mut buf);
+        serial.on_tx_empty();
+        assert_eq!(serial.tx_pending(), 0);
+        let port = serial.release();
+        assert_eq!(port.sent.iter().copied().collect::<Vec<u8>>(), b"4567");
+    }
+}
@@ -0,0 +1,134 @@
+//! Byte-level access to deques of plain-old-data elements, behind the `bytemuck` feature,
+//! for handing sample buffers to DMA engines and flash writers without copying.
+//!
+//! ```
+//! use bare_metal_deque::{BareMetalDeque, PodRingView};
+//!
+//! let mut samples = BareMetalDeque::<u16, 4>::new();
+//! samples.push_back(0x0102);
+//! samples.push_back(0x0304);
+//! let (front, back) = samples.as_bytes();
+//! assert_eq!(front, &[0x02, 0x01, 0x04, 0x03][..]);
+//! assert!(back.is_empty());
+//!
+//! // A ring of u16s that something else (a DMA engine, say) laid out in memory.
+//! let region: [u16; 4] = [3, 4, 1, 2];
+//! let view = PodRingView::<u16>::new(bytemuck::cast_slice(&region), 2, 4).unwrap();
+//! assert_eq!(view.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+//! ```
+
+use bytemuck::{Pod, PodCastError};
+
+use crate::BareMetalDeque;
+
+impl<T: Copy + Default + Pod, const MAX_STORED: usize> BareMetalDeque<T, MAX_STORED> {
+    /// Returns the bytes of the logical contents as two contiguous regions, front first.
+    pub fn as_bytes(&self) -> (&[u8], &[u8]) {
+        let (front, back) = self.as_slices();
+        (bytemuck::cast_slice(front), bytemuck::cast_slice(back))
+    }
+
+    /// Returns the bytes of the logical contents as two mutable contiguous regions, front first.
+    pub fn as_mut_bytes(&mut self) -> (&mut [u8], &mut [u8]) {
+        let (front, back) = self.as_mut_slices();
+        (bytemuck::cast_slice_mut(front), bytemuck::cast_slice_mut(back))
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ViewError {
+    /// The bytes are misaligned or not a whole number of elements.
+    Cast(PodCastError),
+    /// `start` or `len` lie outside the region.
+    OutOfBounds,
+}
+
+impl From<PodCastError> for ViewError {
+    fn from(e: PodCastError) -> Self {
+        ViewError::Cast(e)
+    }
+}
+
+/// A read-only deque view over a ring of `T` stored in a borrowed byte region.
+#[derive(Copy, Clone, Debug)]
+pub struct PodRingView<'a, T> {
+    storage: &'a [T],
+    start: usize,
+    len: usize,
+}
+
+impl<'a, T: Pod> PodRingView<'a, T> {
+    /// Views `bytes` as a ring of `T` whose logical front is at element `start` and which
+    /// holds `len` elements. Fails if `bytes` is misaligned or not a whole number of
+    /// elements, or if `start` or `len` lie outside the region.
+    pub fn new(bytes: &'a [u8], start: usize, len: usize) -> Result<Self, ViewError> {
+        let storage: &[T] = bytemuck::try_cast_slice(bytes)?;
+        if len > storage.len() || (start >= storage.len() && !storage.is_empty()) {
+            return Err(ViewError::OutOfBounds);
+        }
+        Ok(Self { storage, start, len })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        if index < self.len {
+            Some(&self.storage[(self.start + index) % self.storage.len()])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the logical contents as two contiguous slices, front first.
+    pub fn as_slices(&self) -> (&'a [T], &'a [T]) {
+        let first_len = self.len.min(self.storage.len() - self.start);
+        (&self.storage[self.start..self.start + first_len], &self.storage[..self.len - first_len])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + 'a {
+        let (front, back) = self.as_slices();
+        front.iter().chain(back.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapped_bytes_test() {
+        let mut q = BareMetalDeque::<u32, 3>::new();
+        for x in [1, 2, 3] {
+            q.push_back(x);
+        }
+        q.pop_front();
+        q.push_back(4);
+        let (front, back) = q.as_bytes();
+        assert_eq!(front, &[2, 0, 0, 0, 3, 0, 0, 0][..]);
+        assert_eq!(back, &[4, 0, 0, 0][..]);
+
+        q.as_mut_bytes().1[1] = 1;
+        assert_eq!(q.back(), Some(0x104));
+    }
+
+    #[test]
+    fn view_test() {
+        let region: [u32; 3] = [30, 10, 20];
+        let bytes: &[u8] = bytemuck::cast_slice(&region);
+        let view = PodRingView::<u32>::new(bytes, 1, 3).unwrap();
+        assert_eq!(view.as_slices(), (&[10, 20][..], &[30][..]));
+        assert_eq!(view.get(2), Some(&30));
+        assert_eq!(view.get(3), None);
+
+        assert!(matches!(PodRingView::<u32>::new(&bytes[1..5], 0, 1), Err(ViewError::Cast(_))));
+        assert_eq!(PodRingView::<u32>::new(bytes, 3, 0).unwrap_err(), ViewError::OutOfBounds);
+        assert_eq!(PodRingView::<u32>::new(bytes, 0, 4).unwrap_err(), ViewError::OutOfBounds);
+        assert!(PodRingView::<u32>::new(&bytes[..0], 0, 0).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,428 @@
+//! Slice-based reading and writing for byte deques, the usual shape of a UART or USB
+//! driver buffer. Bulk operations copy at most two contiguous segments rather than
+//! moving one byte at a time.
+//!
+//! ```
+//! use bare_metal_deque::ByteDeque;
+//!
+//! let mut rx = ByteDeque::<8>::new();
+//! assert_eq!(rx.write(b"hello, world"), 8);
+//! let mut buf = [0; 5];
+//! assert_eq!(rx.read(&mut buf), 5);
+//! assert_eq!(&buf, b"hello");
+//! assert_eq!(rx.write(b"!!!"), 3);
+//! assert_eq!(rx.peek(&mut buf), 5);
+//! assert_eq!(&buf, b", w!!");
+//! ```
+
+use core::fmt;
+
+use crate::BareMetalDeque;
+
+const HEX_DUMP_WIDTH: usize = 16;
+
+/// A deque of bytes.
+pub type ByteDeque<const N: usize> = BareMetalDeque<u8, N>;
+
+/// Primitive integers that can be read from and written to a byte deque in either byte order.
+pub trait WireInt: Copy {
+    type Bytes: AsRef<[u8]> + AsMut<[u8]> + Default;
+
+    fn from_le_bytes(bytes: Self::Bytes) -> Self;
+    fn from_be_bytes(bytes: Self::Bytes) -> Self;
+    fn to_le_bytes(self) -> Self::Bytes;
+    fn to_be_bytes(self) -> Self::Bytes;
+}
+
+macro_rules! impl_wire_int {
+    ($($t:ty),*) => {
+        $(impl WireInt for $t {
+            type Bytes = [u8; core::mem::size_of::<$t>()];
+
+            fn from_le_bytes(bytes: Self::Bytes) -> Self {
+                <$t>::from_le_bytes(bytes)
+            }
+
+            fn from_be_bytes(bytes: Self::Bytes) -> Self {
+                <$t>::from_be_bytes(bytes)
+            }
+
+            fn to_le_bytes(self) -> Self::Bytes {
+                <$t>::to_le_bytes(self)
+            }
+
+            fn to_be_bytes(self) -> Self::Bytes {
+                <$t>::to_be_bytes(self)
+            }
+        })*
+    };
+}
+
+impl_wire_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl<const N: usize> BareMetalDeque<u8, N> {
+    /// Appends as much of `src` as fits, returning the number of bytes written.
+    pub fn write(&mut self, src: &[u8]) -> usize {
+        self.extend_from_slice(src)
+    }
+
+    /// Copies bytes from the front into `dst` without removing them, returning the
+    /// number of bytes copied.
+    pub fn peek(&self, dst: &mut [u8]) -> usize {
+        self.copy_to_slice(dst)
+    }
+
+    /// Removes bytes from the front into `dst`, returning the number of bytes read.
+    pub fn read(&mut self, dst: &mut [u8]) -> usize {
+        self.pop_front_into(dst)
+    }
+
+    /// Removes the next complete line, up to and including its `\n` (so a `\r\n`
+    /// terminator is kept whole), copies it into `dst` and returns its length. Returns
+    /// `None`, leaving the deque unchanged, if no complete line is buffered or the line
+    /// does not fit in `dst`; `discard_line()` drops such an overlong line.
+    pub fn pop_line(&mut self, dst: &mut [u8]) -> Option<usize> {
+        let len = self.find_byte(b'\n')? + 1;
+        let dst = dst.get_mut(..len)?;
+        self.peek(dst);
+        self.discard(len);
+        Some(len)
+    }
+
+    /// Removes the next complete line, including its terminator, without copying it,
+    /// returning its length, or `None` if no complete line is buffered.
+    pub fn discard_line(&mut self) -> Option<usize> {
+        let len = self.find_byte(b'\n')? + 1;
+        self.discard(len);
+        Some(len)
+    }
+
+    /// Appends `value` in little-endian order. Returns `false`, writing nothing, if it does not fit.
+    pub fn write_int_le<I: WireInt>(&mut self, value: I) -> bool {
+        self.write_all(value.to_le_bytes().as_ref())
+    }
+
+    /// Appends `value` in big-endian order. Returns `false`, writing nothing, if it does not fit.
+    pub fn write_int_be<I: WireInt>(&mut self, value: I) -> bool {
+        self.write_all(value.to_be_bytes().as_ref())
+    }
+
+    /// Removes a little-endian integer from the front, or returns `None` if too few bytes are buffered.
+    pub fn read_int_le<I: WireInt>(&mut self) -> Option<I> {
+        self.read_bytes().map(I::from_le_bytes)
+    }
+
+    /// Removes a big-endian integer from the front, or returns `None` if too few bytes are buffered.
+    pub fn read_int_be<I: WireInt>(&mut self) -> Option<I> {
+        self.read_bytes().map(I::from_be_bytes)
+    }
+
+    pub fn write_u16_le(&mut self, value: u16) -> bool {
+        self.write_int_le(value)
+    }
+
+    pub fn write_u16_be(&mut self, value: u16) -> bool {
+        self.write_int_be(value)
+    }
+
+    pub fn write_u32_le(&mut self, value: u32) -> bool {
+        self.write_int_le(value)
+    }
+
+    pub fn write_u32_be(&mut self, value: u32) -> bool {
+        self.write_int_be(value)
+    }
+
+    pub fn read_u16_le(&mut self) -> Option<u16> {
+        self.read_int_le()
+    }
+
+    pub fn read_u16_be(&mut self) -> Option<u16> {
+        self.read_int_be()
+    }
+
+    pub fn read_u32_le(&mut self) -> Option<u32> {
+        self.read_int_le()
+    }
+
+    pub fn read_u32_be(&mut self) -> Option<u32> {
+        self.read_int_be()
+    }
+
+    fn write_all(&mut self, src: &[u8]) -> bool {
+        if src.len() > N - self.size {
+            return false;
+        }
+        self.write(src);
+        true
+    }
+
+    fn read_bytes<B: AsMut<[u8]> + Default>(&mut self) -> Option<B> {
+        let mut bytes = B::default();
+        if bytes.as_mut().len() > self.size {
+            return None;
+        }
+        self.read(bytes.as_mut());
+        Some(bytes)
+    }
+
+    /// Logical index of the first occurrence of `b`, scanning a word at a time.
+    pub fn find_byte(&self, b: u8) -> Option<usize> {
+        self.find_byte_from(b, 0)
+    }
+
+    /// Logical index of the first occurrence of `needle`, which may span the wrap point.
+    /// An empty needle is found at index 0.
+    pub fn find(&self, needle: &[u8]) -> Option<usize> {
+        let Some((&first, rest)) = needle.split_first() else {
+            return Some(0);
+        };
+        let mut from = 0;
+        loop {
+            let at = self.find_byte_from(first, from)?;
+            if at + needle.len() > self.size {
+                return None;
+            }
+            if rest.iter().enumerate().all(|(i, b)| self[at + 1 + i] == *b) {
+                return Some(at);
+            }
+            from = at + 1;
+        }
+    }
+
+    fn find_byte_from(&self, b: u8, from: usize) -> Option<usize> {
+        let (front, back) = self.as_slices();
+        if from < front.len() {
+            if let Some(i) = memchr(b, &front[from..]) {
+                return Some(from + i);
+            }
+            memchr(b, back).map(|i| front.len() + i)
+        } else {
+            let skip = from - front.len();
+            memchr(b, back.get(skip..)?).map(|i| from + i)
+        }
+    }
+
+    /// Returns an adapter that displays the contents in `hexdump -C` style: an offset,
+    /// sixteen bytes in hex, and their printable ASCII characters per line.
+    pub fn hex_dump(&self) -> HexDump<'_, N> {
+        HexDump(self)
+    }
+}
+
+/// Index of the first `b` in `haystack`. Whole words are tested for a matching byte with
+/// the usual has-zero-byte trick, and only a word that has one is searched bytewise.
+fn memchr(b: u8, haystack: &[u8]) -> Option<usize> {
+    const WORD: usize = size_of::<usize>();
+    const LOW_BITS: usize = usize::from_ne_bytes([0x01; WORD]);
+    const HIGH_BITS: usize = usize::from_ne_bytes([0x80; WORD]);
+    let repeated = LOW_BITS * b as usize;
+    let mut words = haystack.chunks_exact(WORD);
+    for (i, word) in words.by_ref().enumerate() {
+        let x = usize::from_ne_bytes(word.try_into().unwrap()) ^ repeated;
+        if x.wrapping_sub(LOW_BITS) & !x & HIGH_BITS != 0 {
+            return word.iter().position(|c| *c == b).map(|j| i * WORD + j);
+        }
+    }
+    let tail = words.remainder();
+    tail.iter().position(|c| *c == b).map(|j| haystack.len() - tail.len() + j)
+}
+
+/// Formats text directly into the deque, as in `write!(q, "T={}", temp)`. Text that does
+/// not fit is dropped, truncating at a character boundary, and the write reports
+/// `fmt::Error`; everything before the truncation point stays in the deque.
+impl<const N: usize> fmt::Write for BareMetalDeque<u8, N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let free = N - self.size;
+        if s.len() <= free {
+            self.write(s.as_bytes());
+            return Ok(());
+        }
+        let fits = (0..=free).rev().find(|i| s.is_char_boundary(*i)).unwrap_or(0);
+        self.write(&s.as_bytes()[..fits]);
+        Err(fmt::Error)
+    }
+}
+
+pub struct HexDump<'a, const N: usize>(&'a ByteDeque<N>);
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+const HEX_DUMP_LINE_MAX: usize = 9 + 2 + 3 * HEX_DUMP_WIDTH + 2 + HEX_DUMP_WIDTH + 2;
+
+impl<const N: usize> HexDump<'_, N> {
+    /// Passes each rendered line, including its trailing newline, to `output`.
+    pub(crate) fn for_each_line<E, F: FnMut(&str) -> Result<(), E>>(&self, mut output: F) -> Result<(), E> {
+        let mut line = [0; HEX_DUMP_LINE_MAX];
+        for line_start in (0..self.0.len()).step_by(HEX_DUMP_WIDTH) {
+            let len = self.render_line(line_start, &mut line);
+            // Every rendered byte is ASCII.
+            output(core::str::from_utf8(&line[..len]).unwrap_or_default())?;
+        }
+        Ok(())
+    }
+
+    fn render_line(&self, line_start: usize, line: &mut [u8; HEX_DUMP_LINE_MAX]) -> usize {
+        let bytes = self.0;
+        let line_len = (bytes.len() - line_start).min(HEX_DUMP_WIDTH);
+        let mut len = 0;
+        let mut put = |b: u8| {
+            line[len] = b;
+            len += 1;
+        };
+        for shift in (0..8).rev() {
+            put(HEX_DIGITS[(line_start >> (4 * shift)) & 0xF]);
+        }
+        put(b' ');
+        for i in 0..HEX_DUMP_WIDTH {
+            if i % 8 == 0 {
+                put(b' ');
+            }
+            if i < line_len {
+                let b = bytes[line_start + i];
+                put(HEX_DIGITS[(b >> 4) as usize]);
+                put(HEX_DIGITS[(b & 0xF) as usize]);
+            } else {
+                put(b' ');
+                put(b' ');
+            }
+            put(b' ');
+        }
+        put(b' ');
+        put(b'|');
+        for i in 0..line_len {
+            let b = bytes[line_start + i];
+            put(if b.is_ascii_graphic() || b == b' ' { b } else { b'.' });
+        }
+        put(b'|');
+        put(b'\n');
+        len
+    }
+}
+
+impl<const N: usize> fmt::Display for HexDump<'_, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.for_each_line(|line| f.write_str(line))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapped_read_write_test() {
+        let mut q = ByteDeque::<5>::new();
+        let mut buf = [0; 5];
+        for round in 0..10u8 {
+            let data = [round, round + 1, round + 2];
+            assert_eq!(q.write(&data), 3);
+            assert_eq!(q.len(), 3);
+            assert_eq!(q.read(&mut buf[..2]), 2);
+            assert_eq!(&buf[..2], &data[..2]);
+            assert_eq!(q.read(&mut buf), 1);
+            assert_eq!(buf[0], data[2]);
+            assert!(q.is_empty());
+        }
+
+        assert_eq!(q.write(&[1, 2, 3, 4, 5, 6]), 5);
+        assert_eq!(q.write(&[7]), 0);
+        assert_eq!(q.read(&mut []), 0);
+        assert_eq!(q.read(&mut buf), 5);
+        assert_eq!(buf, [1, 2, 3, 4, 5]);
+        assert_eq!(q.read(&mut buf), 0);
+    }
+
+    #[test]
+    fn zero_capacity_test() {
+        let mut q = ByteDeque::<0>::new();
+        assert_eq!(q.write(b"abc"), 0);
+        assert_eq!(q.read(&mut [0; 3]), 0);
+    }
+
+    #[test]
+    fn pop_line_test() {
+        let mut q = ByteDeque::<16>::new();
+        let mut line = [0; 8];
+        q.write(b"AT\r\nOK\n+CS");
+        assert_eq!(q.pop_line(&mut line), Some(4));
+        assert_eq!(&line[..4], b"AT\r\n");
+        assert_eq!(q.pop_line(&mut line), Some(3));
+        assert_eq!(&line[..3], b"OK\n");
+        assert_eq!(q.pop_line(&mut line), None);
+        assert_eq!(q.len(), 3);
+
+        q.write(b"Q: 99,99\r\n\n");
+        assert_eq!(q.pop_line(&mut line), None);
+        assert_eq!(q.len(), 14);
+        assert_eq!(q.discard_line(), Some(13));
+        assert_eq!(q.pop_line(&mut line), Some(1));
+        assert!(q.is_empty());
+        assert_eq!(q.discard_line(), None);
+    }
+
+    #[test]
+    fn int_test() {
+        let mut q = ByteDeque::<8>::new();
+        assert!(q.write_u16_be(0x1234));
+        assert!(q.write_u32_le(0xDEADBEEF));
+        assert!(q.write_int_le(-2i16));
+        assert!(!q.write_u16_le(1));
+        assert_eq!(q.iter().copied().collect::<Vec<u8>>(), vec![0x12, 0x34, 0xEF, 0xBE, 0xAD, 0xDE, 0xFE, 0xFF]);
+
+        assert_eq!(q.read_u16_le(), Some(0x3412));
+        assert_eq!(q.read_u32_be(), Some(0xEFBEADDE));
+        assert_eq!(q.read_int_le::<u32>(), None);
+        assert_eq!(q.read_int_le::<i16>(), Some(-2));
+        assert!(q.is_empty());
+
+        assert!(q.write_int_be(0x0102030405060708u64));
+        assert_eq!(q.read_int_be::<u64>(), Some(0x0102030405060708));
+    }
+
+    #[test]
+    fn hex_dump_test() {
+        let mut q = ByteDeque::<32>::new();
+        assert_eq!(format!("{}", q.hex_dump()), "");
+        q.write(b"Hello, world!\n\x00\x7f\xffABC");
+        assert_eq!(
+            format!("{}", q.hex_dump()),
+            "00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a 00 7f  |Hello, world!...|\n\
+             00000010  ff 41 42 43                                       |.ABC|\n"
+        );
+    }
+
+    #[test]
+    fn fmt_write_test() {
+        use core::fmt::Write;
+
+        let mut q = ByteDeque::<12>::new();
+        write!(q, "T={}", 215).unwrap();
+        assert_eq!(q.len(), 5);
+        assert!(write!(q, " °C, rising").is_err());
+        let mut buf = [0; 12];
+        let len = q.peek(&mut buf);
+        assert_eq!(core::str::from_utf8(&buf[..len]).unwrap(), "T=215 °C, r");
+        assert!(write!(q, "x").is_err());
+        assert!(write!(q, "").is_ok());
+    }
+
+    #[test]
+    fn find_test() {
+        let mut q = ByteDeque::<24>::new();
+        q.write(&[0; 20]);
+        q.discard(20);
+        q.write(b"abc\r\nde\x80fgh:xy:z;");
+        assert_eq!(q.find_byte(b'a'), Some(0));
+        assert_eq!(q.find_byte(b'\n'), Some(4));
+        assert_eq!(q.find_byte(0x80), Some(7));
+        assert_eq!(q.find_byte(b';'), Some(16));
+        assert_eq!(q.find_byte(b'q'), None);
+
+        assert_eq!(q.find(b""), Some(0));
+        assert_eq!(q.find(b"\r\nd"), Some(3));
+        assert_eq!(q.find(b":z"), Some(14));
+        assert_eq!(q.find(b";x"), None);
+        assert_eq!(q.find(b"cd"), None);
+        assert_eq!(ByteDeque::<0>::new().find_byte(0), None);
+    }
+}
@@ -0,0 +1,223 @@
+//! Transmit and receive buffering for [`embedded_can::Frame`] types, behind the
+//! `embedded-can` feature.
+//!
+//! [`CanTxQueue`] releases frames in bus arbitration order, lowest ID first, so a
+//! driver refilling its transmit mailbox never holds a high-priority frame behind a
+//! low-priority one. Frames with equal IDs go out in the order they were queued.
+//! [`CanRxQueue`] applies an ID and mask acceptance filter as frames arrive, so
+//! unwanted traffic never takes up space.
+//!
+//! Frame types must be `Copy` and `Default` to be stored.
+
+use embedded_can::{Frame, Id};
+
+use crate::BareMetalDeque;
+
+#[derive(Copy, Clone, Debug)]
+pub struct CanTxQueue<F: Default, const MAX_STORED: usize> {
+    frames: BareMetalDeque<F, MAX_STORED>,
+}
+
+impl<F: Frame + Copy + Default, const MAX_STORED: usize> Default for CanTxQueue<F, MAX_STORED> {
+    fn default() -> Self {
+        Self { frames: BareMetalDeque::new() }
+    }
+}
+
+impl<F: Frame + Copy + Default, const MAX_STORED: usize> CanTxQueue<F, MAX_STORED> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Queues `frame` behind any frames of equal or higher priority, or hands it back if
+    /// the queue is full.
+    pub fn push(&mut self, frame: F) -> Result<(), F> {
+        if self.frames.is_full() {
+            return Err(frame);
+        }
+        let id = frame.id();
+        let at = self.frames.iter().position(|queued| queued.id() > id).unwrap_or(self.frames.len());
+        self.frames.insert(at, frame);
+        Ok(())
+    }
+
+    /// The highest-priority frame, without removing it.
+    pub fn peek(&self) -> Option<F> {
+        self.frames.front()
+    }
+
+    /// Removes the highest-priority frame.
+    pub fn pop(&mut self) -> Option<F> {
+        self.frames.pop_front()
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Filter {
+    id: Id,
+    mask: u32,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct CanRxQueue<F: Default, const MAX_STORED: usize> {
+    frames: BareMetalDeque<F, MAX_STORED>,
+    filter: Option<Filter>,
+    overruns: u32,
+}
+
+impl<F: Frame + Copy + Default, const MAX_STORED: usize> Default for CanRxQueue<F, MAX_STORED> {
+    fn default() -> Self {
+        Self { frames: BareMetalDeque::new(), filter: None, overruns: 0 }
+    }
+}
+
+fn raw(id: Id) -> u32 {
+    match id {
+        Id::Standard(id) => u32::from(id.as_raw()),
+        Id::Extended(id) => id.as_raw(),
+    }
+}
+
+impl<F: Frame + Copy + Default, const MAX_STORED: usize> CanRxQueue<F, MAX_STORED> {
+    /// Creates a queue that accepts every frame.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accepts only frames with the same ID format as `id` whose ID bits selected by
+    /// `mask` equal those of `id`.
+    pub fn set_filter(&mut self, id: Id, mask: u32) {
+        self.filter = Some(Filter { id, mask });
+    }
+
+    /// Accepts every frame again.
+    pub fn clear_filter(&mut self) {
+        self.filter = None;
+    }
+
+    /// Whether the filter accepts `id`.
+    pub fn accepts(&self, id: Id) -> bool {
+        self.filter.is_none_or(|filter| {
+            matches!((filter.id, id), (Id::Standard(_), Id::Standard(_)) | (Id::Extended(_), Id::Extended(_)))
+                && raw(filter.id) & filter.mask == raw(id) & filter.mask
+        })
+    }
+
+    /// Queues `frame` if the filter accepts it, returning whether it was queued. An
+    /// accepted frame that does not fit is dropped and counted as an overrun.
+    pub fn push(&mut self, frame: F) -> bool {
+        if !self.accepts(frame.id()) {
+            return false;
+        }
+        if self.frames.try_push_back(frame).is_err() {
+            self.overruns = self.overruns.wrapping_add(1);
+            return false;
+        }
+        true
+    }
+
+    /// Removes the oldest received frame.
+    pub fn pop(&mut self) -> Option<F> {
+        self.frames.pop_front()
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Number of accepted frames dropped because the queue was full.
+    pub fn overruns(&self) -> u32 {
+        self.overruns
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_can::{ExtendedId, StandardId};
+
+    use super::*;
+
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    struct TestFrame {
+        id: Id,
+        byte: u8,
+    }
+
+    impl Default for TestFrame {
+        fn default() -> Self {
+            Self { id: Id::Standard(StandardId::ZERO), byte: 0 }
+        }
+    }
+
+    impl Frame for TestFrame {
+        fn new(id: impl Into<Id>, data: &[u8]) -> Option<Self> {
+            Some(Self { id: id.into(), byte: *data.first().unwrap_or(&0) })
+        }
+
+        fn new_remote(id: impl Into<Id>, _dlc: usize) -> Option<Self> {
+            Self::new(id, &[])
+        }
+
+        fn is_extended(&self) -> bool {
+            matches!(self.id, Id::Extended(_))
+        }
+
+        fn is_remote_frame(&self) -> bool {
+            false
+        }
+
+        fn id(&self) -> Id {
+            self.id
+        }
+
+        fn dlc(&self) -> usize {
+            1
+        }
+
+        fn data(&self) -> &[u8] {
+            core::slice::from_ref(&self.byte)
+        }
+    }
+
+    fn standard(id: u16, byte: u8) -> TestFrame {
+        TestFrame::new(StandardId::new(id).unwrap(), &[byte]).unwrap()
+    }
+
+    #[test]
+    fn tx_priority_test() {
+        let mut tx = CanTxQueue::<TestFrame, 3>::new();
+        tx.push(standard(0x200, 1)).unwrap();
+        tx.push(standard(0x100, 2)).unwrap();
+        tx.push(standard(0x200, 3)).unwrap();
+        assert_eq!(tx.push(standard(0x000, 4)), Err(standard(0x000, 4)));
+        let order: Vec<_> = core::iter::from_fn(|| tx.pop()).map(|f| f.byte).collect();
+        assert_eq!(order, vec![2, 1, 3]);
+    }
+
+    #[test]
+    fn rx_filter_test() {
+        let mut rx = CanRxQueue::<TestFrame, 1>::new();
+        rx.set_filter(Id::Standard(StandardId::new(0x120).unwrap()), 0x7F0);
+        assert!(!rx.push(standard(0x130, 1)));
+        let extended = TestFrame::new(ExtendedId::new(0x125).unwrap(), &[2]).unwrap();
+        assert!(!rx.push(extended));
+        assert!(rx.push(standard(0x12F, 3)));
+        assert!(!rx.push(standard(0x121, 4)));
+        assert_eq!(rx.overruns(), 1);
+        rx.clear_filter();
+        assert_eq!(rx.pop().map(|f| f.byte), Some(3));
+        assert!(rx.push(extended));
+    }
+}
@@ -0,0 +1,106 @@
+//! Per-channel access to interleaved samples, such as stereo audio stored `L R L R ...`,
+//! without deinterleaving into separate buffers.
+//!
+//! Channel `ch` of a deque holding `stride` interleaved channels is the elements at logical
+//! indices `ch`, `ch + stride`, `ch + 2 * stride`, and so on.
+//!
+//! ```
+//! use bare_metal_deque::deque;
+//!
+//! let mut audio = deque![10, -10, 20, -20, 30; cap = 8];
+//! let left = audio.channel_view(0, 2);
+//! assert_eq!(left.len(), 3);
+//! assert_eq!(left.get(2), Some(&30));
+//! assert_eq!(audio.channel_iter(1, 2).sum::<i32>(), -30);
+//!
+//! audio.channel_iter_mut(1, 2).for_each(|s| *s /= 2);
+//! assert_eq!(audio.view(..), [10, -5, 20, -10, 30]);
+//! ```
+
+use crate::{BareMetalDeque, DequeView};
+
+/// A view of one channel of interleaved samples.
+#[derive(Debug)]
+pub struct ChannelView<'a, T> {
+    samples: DequeView<'a, T>,
+    channel: usize,
+    stride: usize,
+}
+
+// Implemented by hand for the same reason as `DequeView`.
+impl<T> Clone for ChannelView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ChannelView<'_, T> {}
+
+impl<'a, T> ChannelView<'a, T> {
+    /// Number of samples in the channel.
+    pub fn len(&self) -> usize {
+        self.samples.len().saturating_sub(self.channel).div_ceil(self.stride)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The channel's `index`th sample.
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        index.checked_mul(self.stride).and_then(|i| self.samples.get(i + self.channel))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + 'a {
+        self.samples.iter().skip(self.channel).step_by(self.stride)
+    }
+}
+
+fn check_channel(channel: usize, stride: usize) {
+    if channel >= stride {
+        panic!("Channel must be less than the stride");
+    }
+}
+
+impl<T: Copy + Clone + Default, const MAX_STORED: usize> BareMetalDeque<T, MAX_STORED> {
+    /// Iterates over channel `channel` of `stride` interleaved channels. Panics if
+    /// `channel >= stride`.
+    pub fn channel_iter(&self, channel: usize, stride: usize) -> impl Iterator<Item = &T> {
+        check_channel(channel, stride);
+        self.iter().skip(channel).step_by(stride)
+    }
+
+    /// Iterates mutably over channel `channel` of `stride` interleaved channels. Panics if
+    /// `channel >= stride`.
+    pub fn channel_iter_mut(&mut self, channel: usize, stride: usize) -> impl Iterator<Item = &mut T> {
+        check_channel(channel, stride);
+        let (front, back) = self.as_mut_slices();
+        front.iter_mut().chain(back).skip(channel).step_by(stride)
+    }
+
+    /// A view of channel `channel` of `stride` interleaved channels. Panics if
+    /// `channel >= stride`.
+    pub fn channel_view(&self, channel: usize, stride: usize) -> ChannelView<'_, T> {
+        check_channel(channel, stride);
+        ChannelView { samples: self.view(..), channel, stride }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn channel_test() {
+        let mut q = BareMetalDeque::<u8, 7>::new();
+        q.extend_from_slice(&[0; 5]);
+        q.discard(5);
+        q.extend_from_slice(&[0, 1, 2, 10, 11, 12, 20]);
+        let views: Vec<_> = (0..3).map(|ch| q.channel_view(ch, 3)).collect();
+        assert_eq!(views.iter().map(ChannelView::len).collect::<Vec<_>>(), vec![3, 2, 2]);
+        assert_eq!(views[2].iter().copied().collect::<Vec<_>>(), vec![2, 12]);
+        assert_eq!(views[1].get(1), Some(&11));
+        assert_eq!(views[1].get(2), None);
+        assert!(BareMetalDeque::<u8, 2>::new().channel_view(1, 2).is_empty());
+    }
+}
@@ -0,0 +1,135 @@
+//! Fixed-size array iterators over the logical contents, which may span the wrap point.
+//!
+//! ```
+//! use bare_metal_deque::deque;
+//!
+//! let samples = deque![1, 2, 3, 4; cap = 8];
+//! let diffs: Vec<i32> = samples.array_windows().map(|[a, b]| b - a).collect();
+//! assert_eq!(diffs, vec![1, 1, 1]);
+//!
+//! let mut stereo = samples.array_chunks();
+//! assert_eq!(stereo.next(), Some([1, 2]));
+//! assert_eq!(stereo.next(), Some([3, 4]));
+//! assert_eq!(stereo.next(), None);
+//! ```
+
+use crate::{BareMetalDeque, DequeView};
+
+/// Iterator returned by [`BareMetalDeque::array_windows`].
+#[derive(Clone, Debug)]
+pub struct ArrayWindows<'a, T, const K: usize> {
+    view: DequeView<'a, T>,
+    position: usize,
+}
+
+impl<T: Copy + Default, const K: usize> Iterator for ArrayWindows<'_, T, K> {
+    type Item = [T; K];
+
+    fn next(&mut self) -> Option<[T; K]> {
+        if self.position + K > self.view.len() {
+            return None;
+        }
+        let mut window = [T::default(); K];
+        self.view.split_at(self.position).1.copy_to_slice(&mut window);
+        self.position += 1;
+        Some(window)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.view.len() + 1).saturating_sub(self.position + K);
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T: Copy + Default, const K: usize> ExactSizeIterator for ArrayWindows<'_, T, K> {}
+
+/// Iterator returned by [`BareMetalDeque::array_chunks`].
+#[derive(Clone, Debug)]
+pub struct ArrayChunks<'a, T, const K: usize> {
+    chunks: DequeView<'a, T>,
+    remainder: DequeView<'a, T>,
+}
+
+impl<'a, T, const K: usize> ArrayChunks<'a, T, K> {
+    /// The trailing elements, fewer than `K`, that do not make up a whole chunk.
+    pub fn remainder(&self) -> DequeView<'a, T> {
+        self.remainder
+    }
+}
+
+impl<T: Copy + Default, const K: usize> Iterator for ArrayChunks<'_, T, K> {
+    type Item = [T; K];
+
+    fn next(&mut self) -> Option<[T; K]> {
+        if self.chunks.is_empty() {
+            return None;
+        }
+        let (chunk, rest) = self.chunks.split_at(K);
+        let mut result = [T::default(); K];
+        chunk.copy_to_slice(&mut result);
+        self.chunks = rest;
+        Some(result)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.chunks.len() / K;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T: Copy + Default, const K: usize> ExactSizeIterator for ArrayChunks<'_, T, K> {}
+
+impl<T: Copy + Clone + Default, const MAX_STORED: usize> BareMetalDeque<T, MAX_STORED> {
+    /// Iterates over consecutive, non-overlapping runs of `K` elements, front to back, as
+    /// arrays. Elements left over at the end are available from `remainder()`. Panics if
+    /// `K` is 0.
+    pub fn array_chunks<const K: usize>(&self) -> ArrayChunks<'_, T, K> {
+        if K == 0 {
+            panic!("Chunk size must be nonzero");
+        }
+        let (chunks, remainder) = self.split_at(self.len() - self.len() % K);
+        ArrayChunks { chunks, remainder }
+    }
+
+    /// Iterates over every run of `K` consecutive elements, front to back, as arrays.
+    /// Yields nothing if there are fewer than `K` elements. Panics if `K` is 0.
+    pub fn array_windows<const K: usize>(&self) -> ArrayWindows<'_, T, K> {
+        if K == 0 {
+            panic!("Window size must be nonzero");
+        }
+        ArrayWindows { view: self.view(..), position: 0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapped_windows_test() {
+        let mut q = BareMetalDeque::<u8, 5>::new();
+        q.extend_from_slice(&[0, 0, 0, 1, 2]);
+        q.discard(3);
+        q.extend_from_slice(&[3, 4, 5]);
+        let windows = q.array_windows::<3>();
+        assert_eq!(windows.len(), 3);
+        assert_eq!(windows.collect::<Vec<_>>(), vec![[1, 2, 3], [2, 3, 4], [3, 4, 5]]);
+        assert_eq!(q.array_windows::<6>().next(), None);
+        assert_eq!(q.array_windows::<6>().len(), 0);
+    }
+
+    #[test]
+    fn wrapped_chunks_test() {
+        let mut q = BareMetalDeque::<u8, 8>::new();
+        q.extend_from_slice(&[0; 6]);
+        q.discard(6);
+        q.extend_from_slice(b"abcdefg");
+        let mut chunks = q.array_chunks::<3>();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks.remainder(), *b"g");
+        assert_eq!(chunks.next(), Some(*b"abc"));
+        assert_eq!(chunks.next(), Some(*b"def"));
+        assert_eq!(chunks.next(), None);
+        assert!(q.array_chunks::<7>().remainder().is_empty());
+    }
+}
@@ -0,0 +1,190 @@
+//! [Consistent Overhead Byte Stuffing](https://en.wikipedia.org/wiki/Consistent_Overhead_Byte_Stuffing)
+//! framing over byte deques. Each encoded frame is terminated by a `0x00` delimiter,
+//! which never appears inside the encoded data, so a receiver can always resynchronize
+//! at the next delimiter.
+//!
+//! ```
+//! use bare_metal_deque::{ByteDeque, cobs};
+//!
+//! let mut link = ByteDeque::<32>::new();
+//! cobs::encode_into(&[0x11, 0x00, 0x22], &mut link).unwrap();
+//! cobs::encode_into(b"hi", &mut link).unwrap();
+//!
+//! let mut frame = [0; 8];
+//! assert_eq!(cobs::decode_from(&mut link, &mut frame), Some(Ok(3)));
+//! assert_eq!(&frame[..3], &[0x11, 0x00, 0x22]);
+//! assert_eq!(cobs::decode_from(&mut link, &mut frame), Some(Ok(2)));
+//! assert_eq!(&frame[..2], b"hi");
+//! assert_eq!(cobs::decode_from(&mut link, &mut frame), None);
+//! ```
+
+use crate::ByteDeque;
+
+const DELIMITER: u8 = 0x00;
+const MAX_RUN: usize = 254;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CobsError {
+    /// The destination deque cannot hold the whole encoded frame.
+    NoSpace,
+    /// The decoded frame does not fit in the destination buffer.
+    TooLong,
+    /// The received bytes are not a valid COBS encoding.
+    Corrupt,
+    /// The receive deque filled up without a delimiter, so its contents were discarded.
+    Overrun,
+}
+
+/// Number of bytes `frame` occupies once encoded, excluding the delimiter.
+pub fn encoded_len(frame: &[u8]) -> usize {
+    let mut len = 0;
+    encode_runs(frame, |run| len += run.len() + 1);
+    len
+}
+
+/// Appends the encoded `frame` and its delimiter to `tx`, returning the number of bytes
+/// written. Nothing is written if the whole frame does not fit.
+pub fn encode_into<const N: usize>(frame: &[u8], tx: &mut ByteDeque<N>) -> Result<usize, CobsError> {
+    let len = encoded_len(frame) + 1;
+    if len > N - tx.len() {
+        return Err(CobsError::NoSpace);
+    }
+    encode_runs(frame, |run| {
+        tx.push_back(run.len() as u8 + 1);
+        tx.write(run);
+    });
+    tx.push_back(DELIMITER);
+    Ok(len)
+}
+
+/// Removes the next complete frame from `rx` and decodes it into `dst`, returning the
+/// decoded length. Returns `None` if no complete frame is buffered. A frame that is
+/// corrupt or too long for `dst` is still removed, so decoding can resume with the
+/// frame after it. If `rx` fills up without a delimiter, its contents are discarded so
+/// that decoding can resynchronize.
+pub fn decode_from<const N: usize>(rx: &mut ByteDeque<N>, dst: &mut [u8]) -> Option<Result<usize, CobsError>> {
+    loop {
+        let Some(end) = rx.find_byte(DELIMITER) else {
+            if rx.is_full() {
+                rx.discard(rx.len());
+                return Some(Err(CobsError::Overrun));
+            }
+            return None;
+        };
+        if end == 0 {
+            // Back-to-back delimiters carry no frame.
+            rx.pop_front();
+            continue;
+        }
+        let result = decode(rx, end, dst);
+        rx.discard(end + 1);
+        return Some(result);
+    }
+}
+
+fn decode<const N: usize>(rx: &ByteDeque<N>, end: usize, dst: &mut [u8]) -> Result<usize, CobsError> {
+    let mut i = 0;
+    let mut out = 0;
+    while i < end {
+        let code = rx[i] as usize;
+        let run_end = i + code;
+        if run_end > end {
+            return Err(CobsError::Corrupt);
+        }
+        for j in i + 1..run_end {
+            *dst.get_mut(out).ok_or(CobsError::TooLong)? = rx[j];
+            out += 1;
+        }
+        i = run_end;
+        if code <= MAX_RUN && i < end {
+            *dst.get_mut(out).ok_or(CobsError::TooLong)? = 0;
+            out += 1;
+        }
+    }
+    Ok(out)
+}
+
+fn encode_runs<F: FnMut(&[u8])>(frame: &[u8], mut emit: F) {
+    let mut rest = frame;
+    loop {
+        let limit = rest.len().min(MAX_RUN);
+        let run = rest[..limit].iter().position(|b| *b == 0).unwrap_or(limit);
+        emit(&rest[..run]);
+        if run < limit {
+            rest = &rest[run + 1..];
+        } else if run < rest.len() {
+            rest = &rest[run..];
+        } else {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(frame: &[u8]) {
+        let mut link = ByteDeque::<600>::new();
+        let written = encode_into(frame, &mut link).unwrap();
+        assert_eq!(written, link.len());
+        assert!(link.iter().take(written - 1).all(|b| *b != 0));
+        let mut decoded = [0xAA; 600];
+        assert_eq!(decode_from(&mut link, &mut decoded), Some(Ok(frame.len())));
+        assert_eq!(&decoded[..frame.len()], frame);
+        assert!(link.is_empty());
+    }
+
+    #[test]
+    fn round_trip_test() {
+        round_trip(&[]);
+        round_trip(&[0]);
+        round_trip(&[0, 0, 1, 0]);
+        round_trip(&[1, 2, 3]);
+        let mut long = [0u8; 520];
+        for (i, b) in long.iter_mut().enumerate() {
+            *b = (i % 255 + 1) as u8;
+        }
+        round_trip(&long[..253]);
+        round_trip(&long[..254]);
+        round_trip(&long[..255]);
+        round_trip(&long);
+        long[254] = 0;
+        round_trip(&long);
+    }
+
+    #[test]
+    fn known_encoding_test() {
+        let mut link = ByteDeque::<8>::new();
+        encode_into(&[0x11, 0x22, 0x00, 0x33], &mut link).unwrap();
+        assert_eq!(link.iter().copied().collect::<Vec<u8>>(), vec![0x03, 0x11, 0x22, 0x02, 0x33, 0x00]);
+        assert_eq!(encode_into(&[1, 2], &mut link), Err(CobsError::NoSpace));
+        assert_eq!(link.len(), 6);
+    }
+
+    #[test]
+    fn resync_test() {
+        let mut link = ByteDeque::<32>::new();
+        link.write(&[0x05, 0x11, 0x00, 0x00, 0x02, 0x22, 0x00]);
+        encode_into(b"abcdef", &mut link).unwrap();
+        let mut frame = [0; 4];
+        assert_eq!(decode_from(&mut link, &mut frame), Some(Err(CobsError::Corrupt)));
+        assert_eq!(decode_from(&mut link, &mut frame), Some(Ok(1)));
+        assert_eq!(frame[0], 0x22);
+        assert_eq!(decode_from(&mut link, &mut frame), Some(Err(CobsError::TooLong)));
+        assert_eq!(decode_from(&mut link, &mut frame), None);
+        assert!(link.is_empty());
+    }
+
+    #[test]
+    fn overrun_test() {
+        let mut link = ByteDeque::<8>::new();
+        link.write(&[0x07; 8]);
+        let mut frame = [0; 4];
+        assert_eq!(decode_from(&mut link, &mut frame), Some(Err(CobsError::Overrun)));
+        assert!(link.is_empty());
+        encode_into(b"ok", &mut link).unwrap();
+        assert_eq!(decode_from(&mut link, &mut frame), Some(Ok(2)));
+        assert_eq!(&frame[..2], b"ok");
+    }
+}
@@ -0,0 +1,68 @@
+//! A deque for sharing with interrupt handlers on Cortex-M, behind the `cortex-m` feature.
+//! Every operation runs inside `cortex_m::interrupt::free`, so it can be used from thread
+//! mode and handlers alike without further locking.
+//!
+//! ```no_run
+//! use bare_metal_deque::InterruptFreeDeque;
+//!
+//! static RX: InterruptFreeDeque<u8, 64> = InterruptFreeDeque::new(0);
+//!
+//! // In the UART interrupt handler:
+//! let _ = RX.try_push_back(b'x');
+//!
+//! // In the main loop:
+//! while let Some(byte) = RX.pop_front() {
+//!     // ...
+//! }
+//! ```
+
+use core::cell::RefCell;
+
+use cortex_m::interrupt::{self, Mutex};
+
+use crate::{BareMetalDeque, CapacityError};
+
+pub struct InterruptFreeDeque<T: Default, const MAX_STORED: usize> {
+    deque: Mutex<RefCell<BareMetalDeque<T, MAX_STORED>>>,
+}
+
+impl<T: Copy + Default, const MAX_STORED: usize> InterruptFreeDeque<T, MAX_STORED> {
+    /// Creates an empty deque with unused storage set to `fill`, usable in a `static`.
+    pub const fn new(fill: T) -> Self {
+        Self { deque: Mutex::new(RefCell::new(BareMetalDeque::new_with(fill))) }
+    }
+
+    /// Runs `f` on the deque with interrupts disabled, for operations that must be atomic
+    /// together, such as checking for space and then pushing several elements.
+    pub fn with<R>(&self, f: impl FnOnce(&mut BareMetalDeque<T, MAX_STORED>) -> R) -> R {
+        interrupt::free(|cs| f(&mut self.deque.borrow(cs).borrow_mut()))
+    }
+
+    pub fn try_push_back(&self, value: T) -> Result<(), CapacityError> {
+        self.with(|q| q.try_push_back(value))
+    }
+
+    pub fn try_push_front(&self, value: T) -> Result<(), CapacityError> {
+        self.with(|q| q.try_push_front(value))
+    }
+
+    pub fn pop_front(&self) -> Option<T> {
+        self.with(|q| q.pop_front())
+    }
+
+    pub fn pop_back(&self) -> Option<T> {
+        self.with(|q| q.pop_back())
+    }
+
+    pub fn len(&self) -> usize {
+        self.with(|q| q.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.with(|q| q.is_empty())
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.with(|q| q.is_full())
+    }
+}
@@ -0,0 +1,53 @@
+//! Bitwise (table-free) CRCs for protecting stored frames.
+//!
+//! ```
+//! use bare_metal_deque::crc::{Crc, Crc16, Crc32};
+//!
+//! assert_eq!(Crc16::checksum(b"123456789".iter().copied()), 0x29B1);
+//! assert_eq!(Crc32::checksum(b"123456789".iter().copied()), 0xCBF43926);
+//! ```
+
+pub trait Crc {
+    /// Number of bytes the checksum occupies when stored.
+    const LEN: usize;
+
+    fn checksum<I: IntoIterator<Item = u8>>(bytes: I) -> u32;
+}
+
+/// CRC-16/CCITT-FALSE (polynomial `0x1021`, initial value `0xFFFF`).
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Crc16;
+
+/// CRC-32 as used by Ethernet and zlib (reflected polynomial `0xEDB88320`).
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Crc32;
+
+impl Crc for Crc16 {
+    const LEN: usize = 2;
+
+    fn checksum<I: IntoIterator<Item = u8>>(bytes: I) -> u32 {
+        let mut crc: u16 = 0xFFFF;
+        for b in bytes {
+            crc ^= (b as u16) << 8;
+            for _ in 0..8 {
+                crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x1021 } else { crc << 1 };
+            }
+        }
+        crc as u32
+    }
+}
+
+impl Crc for Crc32 {
+    const LEN: usize = 4;
+
+    fn checksum<I: IntoIterator<Item = u8>>(bytes: I) -> u32 {
+        let mut crc: u32 = 0xFFFF_FFFF;
+        for b in bytes {
+            crc ^= b as u32;
+            for _ in 0..8 {
+                crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+            }
+        }
+        !crc
+    }
+}
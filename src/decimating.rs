@@ -0,0 +1,109 @@
+//! A history buffer that keeps one value per group of `factor` samples, so a long-horizon trend
+//! needs `factor` times less RAM than storing every sample. When full, the oldest stored
+//! sample is dropped.
+//!
+//! ```
+//! use bare_metal_deque::DecimatingDeque;
+//!
+//! let mut trend = DecimatingDeque::<i32, 4>::averaging(3);
+//! for sample in [1, 2, 3, 10, 20, 30, 5] {
+//!     trend.push(sample);
+//! }
+//! assert_eq!(trend.samples().view(..), [2, 20]);
+//! ```
+
+use core::ops::{Add, Div};
+
+use crate::BareMetalDeque;
+
+#[derive(Copy, Clone, Debug)]
+pub struct DecimatingDeque<T: Default, const MAX_STORED: usize> {
+    samples: BareMetalDeque<T, MAX_STORED>,
+    factor: u8,
+    pending: u8,
+    /// Folds a sample into the group so far, and turns a completed group into the value
+    /// stored. Function pointers keep `push()` free of the arithmetic bounds averaging needs.
+    combine: fn(T, T) -> T,
+    finish: fn(T, u8) -> T,
+    group: T,
+}
+
+impl<T: Copy + Default, const MAX_STORED: usize> DecimatingDeque<T, MAX_STORED> {
+    /// Stores the first sample of every group of `factor`, once the group is complete.
+    /// Panics if `factor` is 0.
+    pub fn new(factor: u8) -> Self {
+        if factor == 0 {
+            panic!("Decimation factor must be nonzero");
+        }
+        Self {
+            samples: BareMetalDeque::new(),
+            factor,
+            pending: 0,
+            combine: |first, _| first,
+            finish: |first, _| first,
+            group: T::default(),
+        }
+    }
+
+    /// The stored samples, oldest first.
+    pub fn samples(&self) -> &BareMetalDeque<T, MAX_STORED> {
+        &self.samples
+    }
+
+    /// Empties the buffer and starts a new group.
+    pub fn clear(&mut self) {
+        self.samples = BareMetalDeque::new();
+        self.pending = 0;
+    }
+
+    /// Accepts a sample, returning the value stored if it completes a group.
+    pub fn push(&mut self, sample: T) -> Option<T> {
+        self.group = if self.pending == 0 { sample } else { (self.combine)(self.group, sample) };
+        self.pending += 1;
+        if self.pending < self.factor {
+            return None;
+        }
+        self.pending = 0;
+        let value = (self.finish)(self.group, self.factor);
+        if self.samples.is_full() {
+            self.samples.pop_front();
+        }
+        if MAX_STORED > 0 {
+            self.samples.push_back(value);
+        }
+        Some(value)
+    }
+}
+
+impl<T, const MAX_STORED: usize> DecimatingDeque<T, MAX_STORED>
+where
+    T: Copy + Default + Add<Output = T> + Div<Output = T> + From<u8>,
+{
+    /// Stores the mean of every group of `factor` samples. The sum of a group must fit in
+    /// `T`. Panics if `factor` is 0.
+    pub fn averaging(factor: u8) -> Self {
+        Self { combine: |sum, x| sum + x, finish: |sum, n| sum / T::from(n), ..Self::new(factor) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decimation_test() {
+        let mut every_third = DecimatingDeque::<i8, 2>::new(3);
+        let stored: Vec<_> = (1..=10).filter_map(|x| every_third.push(x)).collect();
+        assert_eq!(stored, vec![1, 4, 7]);
+        assert_eq!(every_third.samples().view(..), [4, 7]);
+
+        let mut averaged = DecimatingDeque::<f32, 4>::averaging(2);
+        assert_eq!(averaged.push(1.0), None);
+        assert_eq!(averaged.push(2.0), Some(1.5));
+        averaged.push(5.0);
+        averaged.clear();
+        averaged.push(7.0);
+        assert_eq!(averaged.push(9.0), Some(8.0));
+        assert_eq!(averaged.samples().view(..), [8.0]);
+    }
+}
@@ -0,0 +1,19 @@
+//! `defmt::Format` support, behind the `defmt` feature, so that `defmt::info!("{}", q)`
+//! prints a deque's logical contents front first, as in `[1, 2, 3]`.
+
+use defmt::{Format, Formatter};
+
+use crate::BareMetalDeque;
+
+impl<T: Copy + Default + Format, const MAX_STORED: usize> Format for BareMetalDeque<T, MAX_STORED> {
+    fn format(&self, f: Formatter) {
+        defmt::write!(f, "[");
+        for (i, value) in self.iter().enumerate() {
+            if i > 0 {
+                defmt::write!(f, ", ");
+            }
+            defmt::write!(f, "{}", value);
+        }
+        defmt::write!(f, "]");
+    }
+}
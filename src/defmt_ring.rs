@@ -0,0 +1,181 @@
+//! A [`defmt`](https://defmt.ferrous-systems.com) transport that buffers encoded log
+//! frames in a byte ring, so projects without RTT can drain defmt output over a UART
+//! with bounded memory. Requires the `defmt-logger` feature, and a
+//! [`critical-section`](https://docs.rs/critical-section) implementation for the target.
+//!
+//! Declare the global logger once, in the firmware binary, then call `drain()` from
+//! the main loop:
+//! ```no_run
+//! bare_metal_deque::defmt_ring_logger!(DEFMT_RING, 1024);
+//!
+//! fn main_loop_iteration(uart_write: &mut dyn FnMut(&[u8])) {
+//!     defmt::info!("tick");
+//!     DEFMT_RING.drain(|bytes| uart_write(bytes));
+//! }
+//! ```
+//!
+//! A frame that does not fit in the ring is truncated; with defmt's default `rzcobs`
+//! encoding the host decoder skips the damaged frame and resynchronizes on the next one.
+//! A frame logged from an interrupt while another context is mid-frame is dropped
+//! rather than interleaved.
+
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+use defmt::Encoder;
+
+use crate::ByteDeque;
+
+const DRAIN_CHUNK: usize = 32;
+
+struct State<const N: usize> {
+    ring: ByteDeque<N>,
+    encoder: Encoder,
+    depth: u8,
+}
+
+pub struct DefmtRing<const N: usize> {
+    state: Mutex<RefCell<State<N>>>,
+}
+
+impl<const N: usize> Default for DefmtRing<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> DefmtRing<N> {
+    pub const fn new() -> Self {
+        Self {
+            state: Mutex::new(RefCell::new(State { ring: ByteDeque::new_with(0), encoder: Encoder::new(), depth: 0 })),
+        }
+    }
+
+    /// Number of buffered bytes awaiting `drain()`.
+    pub fn len(&self) -> usize {
+        critical_section::with(|cs| self.state.borrow_ref(cs).ring.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Starts a frame. Called from `defmt::Logger::acquire`.
+    pub fn acquire(&self) {
+        critical_section::with(|cs| {
+            let State { ring, encoder, depth } = &mut *self.state.borrow_ref_mut(cs);
+            *depth += 1;
+            if *depth == 1 {
+                encoder.start_frame(|bytes| {
+                    ring.write(bytes);
+                });
+            }
+        });
+    }
+
+    /// Adds frame data. Called from `defmt::Logger::write`.
+    pub fn write(&self, data: &[u8]) {
+        critical_section::with(|cs| {
+            let State { ring, encoder, depth } = &mut *self.state.borrow_ref_mut(cs);
+            if *depth == 1 {
+                encoder.write(data, |bytes| {
+                    ring.write(bytes);
+                });
+            }
+        });
+    }
+
+    /// Ends a frame. Called from `defmt::Logger::release`.
+    pub fn release(&self) {
+        critical_section::with(|cs| {
+            let State { ring, encoder, depth } = &mut *self.state.borrow_ref_mut(cs);
+            if *depth == 1 {
+                encoder.end_frame(|bytes| {
+                    ring.write(bytes);
+                });
+            }
+            *depth = depth.saturating_sub(1);
+        });
+    }
+
+    /// Passes the buffered bytes to `output` in order, removing them from the ring. Bytes
+    /// are copied out in small chunks, so `output` runs outside the critical section.
+    pub fn drain<F: FnMut(&[u8])>(&self, mut output: F) {
+        let mut remaining = self.len();
+        while remaining > 0 {
+            let mut chunk = [0; DRAIN_CHUNK];
+            let limit = remaining.min(DRAIN_CHUNK);
+            let n = critical_section::with(|cs| self.state.borrow_ref_mut(cs).ring.read(&mut chunk[..limit]));
+            if n == 0 {
+                break;
+            }
+            output(&chunk[..n]);
+            remaining -= n;
+        }
+    }
+}
+
+/// Declares a `static` [`DefmtRing`] of the given capacity and registers it as defmt's
+/// global logger. Invoke it at most once per binary.
+#[macro_export]
+macro_rules! defmt_ring_logger {
+    ($vis:vis $name:ident, $capacity:expr) => {
+        $vis static $name: $crate::DefmtRing<$capacity> = $crate::DefmtRing::new();
+
+        const _: () = {
+            #[defmt::global_logger]
+            struct GlobalDefmtRing;
+
+            unsafe impl defmt::Logger for GlobalDefmtRing {
+                fn acquire() {
+                    $name.acquire();
+                }
+
+                unsafe fn flush() {}
+
+                unsafe fn release() {
+                    $name.release();
+                }
+
+                unsafe fn write(bytes: &[u8]) {
+                    $name.write(bytes);
+                }
+            }
+        };
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drained<const N: usize>(ring: &DefmtRing<N>) -> Vec<u8> {
+        let mut out = Vec::new();
+        ring.drain(|bytes| out.extend_from_slice(bytes));
+        out
+    }
+
+    #[test]
+    fn frame_test() {
+        let ring = DefmtRing::<64>::new();
+        let mut frames = Vec::new();
+        for _ in 0..2 {
+            ring.acquire();
+            ring.write(&[1, 0, 2, 3]);
+            ring.release();
+            let frame = drained(&ring);
+            assert_eq!(frame.last(), Some(&0));
+            frames.push(frame);
+            assert!(ring.is_empty());
+        }
+
+        ring.acquire();
+        ring.write(&[1, 0, 2]);
+        ring.acquire();
+        ring.write(&[9, 9, 9, 9, 9, 9]);
+        ring.release();
+        ring.write(&[3]);
+        ring.release();
+        assert_eq!(drained(&ring), frames[1]);
+    }
+}
@@ -0,0 +1,76 @@
+//! Fractional-delay reads for using a deque of audio samples as a delay or echo line. The
+//! newest sample, at the back, is the write head, and a delay of `d` samples reads between
+//! the two samples around `d` behind it by linear interpolation.
+//!
+//! ```
+//! use bare_metal_deque::deque;
+//!
+//! let line = deque![0.0f32, 1.0, 2.0, 4.0; cap = 4];
+//! assert_eq!(line.read_delayed(0.0), Some(4.0));
+//! assert_eq!(line.read_delayed(0.5), Some(3.0));
+//! assert_eq!(line.read_delayed(2.25), Some(0.75));
+//! assert_eq!(line.read_delayed(3.5), None);
+//!
+//! let line = deque![0i16, 100, 200; cap = 4];
+//! assert_eq!(line.read_delayed_q16(1 << 15), Some(150)); // half a sample back
+//! ```
+
+use crate::BareMetalDeque;
+
+impl<const MAX_STORED: usize> BareMetalDeque<f32, MAX_STORED> {
+    /// The sample `delay` samples behind the newest, interpolated linearly. Returns `None`
+    /// if `delay` is negative, NaN, or reaches past the oldest sample.
+    pub fn read_delayed(&self, delay: f32) -> Option<f32> {
+        if delay.is_nan() || delay < 0.0 {
+            return None;
+        }
+        let whole = delay as usize;
+        let frac = delay - whole as f32;
+        let (newer, older) = self.delayed_pair(whole, frac > 0.0)?;
+        Some(newer + (older - newer) * frac)
+    }
+}
+
+impl<const MAX_STORED: usize> BareMetalDeque<i16, MAX_STORED> {
+    /// Like `read_delayed()`, with `delay` in samples as unsigned Q16.16 fixed point, for
+    /// cores without an FPU. The result is rounded to the nearest integer.
+    pub fn read_delayed_q16(&self, delay: u32) -> Option<i16> {
+        let (whole, frac) = ((delay >> 16) as usize, i64::from(delay & 0xFFFF));
+        let (newer, older) = self.delayed_pair(whole, frac > 0)?;
+        let (newer, older) = (i64::from(newer), i64::from(older));
+        Some((newer + (((older - newer) * frac + (1 << 15)) >> 16)) as i16)
+    }
+}
+
+impl<T: Copy + Clone + Default, const MAX_STORED: usize> BareMetalDeque<T, MAX_STORED> {
+    /// The samples `whole` and, if `need_older`, `whole + 1` behind the newest.
+    fn delayed_pair(&self, whole: usize, need_older: bool) -> Option<(T, T)> {
+        let index = self.size.checked_sub(whole)?.checked_sub(1)?;
+        let newer = *self.get(index)?;
+        if !need_older {
+            return Some((newer, newer));
+        }
+        Some((newer, *self.get(index.checked_sub(1)?)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapped_delay_test() {
+        let mut q = BareMetalDeque::<i16, 4>::new();
+        q.extend_from_slice(&[0, 0, -100]);
+        q.discard(2);
+        q.extend_from_slice(&[100, 300, 301]);
+        assert_eq!(q.read_delayed_q16(0), Some(301));
+        assert_eq!(q.read_delayed_q16(0x1_8000), Some(200));
+        assert_eq!(q.read_delayed_q16(0x2_4000), Some(50));
+        assert_eq!(q.read_delayed_q16(0x3_0000), Some(-100));
+        assert_eq!(q.read_delayed_q16(0x3_0001), None);
+        assert_eq!(q.read_delayed_q16(u32::MAX), None);
+        assert_eq!(BareMetalDeque::<f32, 2>::new().read_delayed(f32::NAN), None);
+        assert_eq!(BareMetalDeque::<f32, 2>::new().read_delayed(f32::INFINITY), None);
+    }
+}
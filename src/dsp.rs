@@ -0,0 +1,176 @@
+//! Element-wise arithmetic over sample windows, for calibration passes and similar
+//! bulk adjustments. Each operation runs over the contiguous segments directly.
+//!
+//! ```
+//! use bare_metal_deque::deque;
+//!
+//! let mut adc = deque![100i16, 200, 300; cap = 8];
+//! adc.calibrate(2, -50);
+//! assert_eq!(adc.view(..), [150, 350, 550]);
+//! adc.saturating_scale(100);
+//! assert_eq!(adc.view(..), [15000, i16::MAX, i16::MAX]);
+//!
+//! // A three-tap FIR filter, accumulating in i32 so the products cannot overflow i16.
+//! let taps = [1i16, 2, 1];
+//! let window = deque![100i16, 200, 300; cap = 3];
+//! assert_eq!(window.dot::<i32>(&taps), 800);
+//! ```
+
+use core::ops::{Add, Mul};
+
+use crate::{BareMetalDeque, DequeView};
+
+/// Integer types with saturating arithmetic, for the `saturating_` operations.
+pub trait Saturating: Copy {
+    fn saturating_add(self, other: Self) -> Self;
+    fn saturating_mul(self, other: Self) -> Self;
+}
+
+macro_rules! impl_saturating {
+    ($($t:ty),*) => {
+        $(impl Saturating for $t {
+            fn saturating_add(self, other: Self) -> Self {
+                <$t>::saturating_add(self, other)
+            }
+
+            fn saturating_mul(self, other: Self) -> Self {
+                <$t>::saturating_mul(self, other)
+            }
+        })*
+    };
+}
+
+impl_saturating!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl<T: Copy> DequeView<'_, T> {
+    /// Sum of products of the elements and `coeffs`, paired from the front, accumulated in
+    /// `A`. Pairing stops at the end of the shorter of the two.
+    pub fn dot<A>(&self, coeffs: &[T]) -> A
+    where
+        A: From<T> + Add<Output = A> + Mul<Output = A> + Default,
+    {
+        let (front, back) = self.as_slices();
+        let (front_coeffs, back_coeffs) = coeffs.split_at(front.len().min(coeffs.len()));
+        front.iter().zip(front_coeffs).chain(back.iter().zip(back_coeffs))
+            .fold(A::default(), |acc, (x, c)| acc + A::from(*x) * A::from(*c))
+    }
+}
+
+impl<T: Copy + Clone + Default, const MAX_STORED: usize> BareMetalDeque<T, MAX_STORED> {
+    /// Sum of products of the contents and `coeffs`, paired from the front, accumulated in
+    /// `A`, as in an FIR filter. Pairing stops at the end of the shorter of the two.
+    pub fn dot<A>(&self, coeffs: &[T]) -> A
+    where
+        A: From<T> + Add<Output = A> + Mul<Output = A> + Default,
+    {
+        self.view(..).dot(coeffs)
+    }
+
+    /// Slides `template` across the contents, yielding the dot product at each offset from
+    /// 0 to `len() - template.len()`, for locating sync words and other known patterns.
+    /// Yields nothing if the template is longer than the contents.
+    pub fn correlate<'a, A>(&'a self, template: &'a [T]) -> impl Iterator<Item = A> + 'a
+    where
+        A: From<T> + Add<Output = A> + Mul<Output = A> + Default,
+    {
+        let offsets = (self.len() + 1).saturating_sub(template.len());
+        (0..offsets).map(move |i| self.view(i..i + template.len()).dot(template))
+    }
+
+    /// Replaces every element `x` with `f(x)`.
+    pub fn apply<F: FnMut(T) -> T>(&mut self, mut f: F) {
+        let (front, back) = self.as_mut_slices();
+        for x in front.iter_mut().chain(back) {
+            *x = f(*x);
+        }
+    }
+
+    /// Adds `offset` to every element.
+    pub fn add_scalar(&mut self, offset: T)
+    where
+        T: Add<Output = T>,
+    {
+        self.apply(|x| x + offset);
+    }
+
+    /// Multiplies every element by `factor`.
+    pub fn scale(&mut self, factor: T)
+    where
+        T: Mul<Output = T>,
+    {
+        self.apply(|x| x * factor);
+    }
+
+    /// Replaces every element `x` with `x * gain + offset`, the usual linear calibration.
+    pub fn calibrate(&mut self, gain: T, offset: T)
+    where
+        T: Add<Output = T> + Mul<Output = T>,
+    {
+        self.apply(|x| x * gain + offset);
+    }
+
+    /// Adds `offset` to every element, clamping at the numeric bounds.
+    pub fn saturating_add_scalar(&mut self, offset: T)
+    where
+        T: Saturating,
+    {
+        self.apply(|x| x.saturating_add(offset));
+    }
+
+    /// Multiplies every element by `factor`, clamping at the numeric bounds.
+    pub fn saturating_scale(&mut self, factor: T)
+    where
+        T: Saturating,
+    {
+        self.apply(|x| x.saturating_mul(factor));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapped_arithmetic_test() {
+        let mut q = BareMetalDeque::<u8, 4>::new();
+        q.extend_from_slice(&[0, 0, 10, 20]);
+        q.discard(2);
+        q.extend_from_slice(&[30, 40]);
+        q.add_scalar(1);
+        q.scale(2);
+        assert_eq!(q.view(..), [22, 42, 62, 82]);
+        q.saturating_add_scalar(200);
+        assert_eq!(q.view(..), [222, 242, 255, 255]);
+        q.saturating_scale(0);
+        assert_eq!(q.view(..), [0; 4]);
+
+        let mut f = BareMetalDeque::<f32, 2>::new();
+        f.push_back(1.5);
+        f.calibrate(2.0, 0.25);
+        assert_eq!(f.front(), Some(3.25));
+    }
+
+    #[test]
+    fn wrapped_dot_test() {
+        let mut q = BareMetalDeque::<u8, 4>::new();
+        q.extend_from_slice(&[0, 0, 200, 200]);
+        q.discard(2);
+        q.extend_from_slice(&[200, 1]);
+        assert_eq!(q.dot::<u32>(&[2, 3, 4, 5]), 400 + 600 + 800 + 5);
+        assert_eq!(q.dot::<u32>(&[1, 1]), 400);
+        assert_eq!(q.dot::<u32>(&[1, 1, 1, 1, 1]), 601);
+    }
+
+    #[test]
+    fn correlate_test() {
+        let mut q = BareMetalDeque::<i8, 6>::new();
+        q.extend_from_slice(&[0, 0, 0, 0, 1, -1]);
+        q.discard(4);
+        q.extend_from_slice(&[1, 1, -1, 1]);
+        let scores: Vec<i32> = q.correlate(&[1, -1, 1]).collect();
+        assert_eq!(scores, vec![3, -1, -1, 3]);
+        let best = scores.iter().enumerate().max_by_key(|(_, s)| **s).map(|(i, _)| i);
+        assert_eq!(best, Some(3));
+        assert_eq!(q.correlate::<i32>(&[1; 7]).count(), 0);
+    }
+}
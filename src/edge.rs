@@ -0,0 +1,84 @@
+//! Edge detection over a stream of logic-level samples, for switch inputs and encoder
+//! channels. A change of level is only accepted once the last `N` samples all agree,
+//! which filters out contact bounce and glitches shorter than `N` samples; with `N = 1`
+//! every change is reported immediately.
+//!
+//! ```
+//! use bare_metal_deque::{Edge, EdgeDetector};
+//!
+//! let mut button = EdgeDetector::<3>::new(false);
+//! let samples = [false, true, false, true, true, true, true, false, false, false];
+//! let edges: Vec<_> = samples.iter().filter_map(|s| button.push(*s)).collect();
+//! assert_eq!(edges, vec![Edge::Rising, Edge::Falling]);
+//! assert!(!button.level());
+//! ```
+
+use crate::BareMetalDeque;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Edge {
+    Rising,
+    Falling,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct EdgeDetector<const N: usize> {
+    history: BareMetalDeque<bool, N>,
+    level: bool,
+}
+
+impl<const N: usize> EdgeDetector<N> {
+    /// Starts from the qualified level `initial`, so that no edge is reported until the
+    /// input settles at the other level.
+    pub fn new(initial: bool) -> Self {
+        if N == 0 {
+            panic!("EdgeDetector must qualify at least one sample");
+        }
+        Self { history: BareMetalDeque::new(), level: initial }
+    }
+
+    /// The current qualified level.
+    pub fn level(&self) -> bool {
+        self.level
+    }
+
+    /// Forgets the sample history and restarts from the qualified level `level`.
+    pub fn reset(&mut self, level: bool) {
+        *self = Self::new(level);
+    }
+
+    /// Adds a sample, returning the edge it completes, if any.
+    pub fn push(&mut self, sample: bool) -> Option<Edge> {
+        if self.history.is_full() {
+            self.history.pop_front();
+        }
+        self.history.push_back(sample);
+        if sample == self.level || !self.history.is_full() || self.history.iter().any(|s| *s != sample) {
+            return None;
+        }
+        self.level = sample;
+        Some(if sample { Edge::Rising } else { Edge::Falling })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn qualification_test() {
+        let mut immediate = EdgeDetector::<1>::new(true);
+        let edges: Vec<_> = [true, false, true, true].iter().map(|s| immediate.push(*s)).collect();
+        assert_eq!(edges, vec![None, Some(Edge::Falling), Some(Edge::Rising), None]);
+
+        let mut qualified = EdgeDetector::<2>::new(false);
+        assert_eq!(qualified.push(true), None);
+        assert_eq!(qualified.push(false), None);
+        assert_eq!(qualified.push(true), None);
+        assert_eq!(qualified.push(true), Some(Edge::Rising));
+        assert_eq!(qualified.push(true), None);
+        assert!(qualified.level());
+        qualified.reset(false);
+        assert_eq!(qualified.push(true), None);
+    }
+}
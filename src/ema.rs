@@ -0,0 +1,83 @@
+//! Exponential moving average over a stream of samples, for smoothing when a window of
+//! past samples would cost too much RAM. The state is a single accumulator.
+//!
+//! The smoothing factor is `1 / 2^shift`, so updates need only shifts and adds, which
+//! suits cores without an FPU or hardware divide.
+//!
+//! ```
+//! use bare_metal_deque::Ema;
+//!
+//! let mut ema = Ema::new(2); // alpha = 1/4
+//! assert_eq!(ema.push(100), 100);
+//! assert_eq!(ema.push(200), 125);
+//! assert_eq!(ema.push(200), 144);
+//! ```
+
+#[derive(Copy, Clone, Debug)]
+pub struct Ema {
+    shift: u32,
+    /// The average, scaled by `2^shift` to keep the fractional bits.
+    accumulator: Option<i64>,
+}
+
+impl Ema {
+    /// Creates an average with smoothing factor `1 / 2^shift`. Panics if `shift > 16`.
+    pub fn new(shift: u32) -> Self {
+        if shift > 16 {
+            panic!("Ema shift must be at most 16");
+        }
+        Self { shift, accumulator: None }
+    }
+
+    pub fn reset(&mut self) {
+        self.accumulator = None;
+    }
+
+    /// Adds a sample and returns the updated average, rounded to the nearest integer.
+    /// The first sample seeds the average.
+    pub fn push(&mut self, sample: i32) -> i32 {
+        let sample = i64::from(sample);
+        let accumulator = match self.accumulator {
+            Some(acc) => acc + sample - self.descale(acc),
+            None => sample << self.shift,
+        };
+        self.accumulator = Some(accumulator);
+        self.round(accumulator)
+    }
+
+    /// The current average, or `None` before the first sample.
+    pub fn value(&self) -> Option<i32> {
+        self.accumulator.map(|acc| self.round(acc))
+    }
+
+    fn descale(&self, accumulator: i64) -> i64 {
+        accumulator >> self.shift
+    }
+
+    fn round(&self, accumulator: i64) -> i32 {
+        let half = (1 << self.shift) >> 1;
+        self.descale(accumulator + half) as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convergence_test() {
+        let mut ema = Ema::new(3);
+        assert_eq!(ema.value(), None);
+        ema.push(-1000);
+        for _ in 0..200 {
+            ema.push(1000);
+        }
+        assert_eq!(ema.value(), Some(1000));
+
+        let mut passthrough = Ema::new(0);
+        assert_eq!(passthrough.push(i32::MIN), i32::MIN);
+        assert_eq!(passthrough.push(i32::MAX), i32::MAX);
+        passthrough.reset();
+        assert_eq!(passthrough.value(), None);
+    }
+}
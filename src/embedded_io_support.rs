@@ -0,0 +1,162 @@
+//! [`embedded-io`](https://docs.rs/embedded-io) support for byte deques and the halves of a
+//! byte [`SpscQueue`](crate::SpscQueue), behind the
+//! `embedded-io` feature, so a deque can be handed to drivers and protocol stacks written
+//! against those traits.
+//!
+//! A deque never blocks: reading from an empty deque returns `Ok(0)`, as at end of file,
+//! and writing to a full deque fails with [`ErrorKind::WriteZero`]. Use `ReadReady` and
+//! `WriteReady` to check beforehand.
+//!
+//! ```
+//! use bare_metal_deque::ByteDeque;
+//! use embedded_io::{BufRead, Read, Write};
+//!
+//! fn send<W: Write>(w: &mut W) -> Result<(), W::Error> {
+//!     w.write_all(b"AT+GMR\r\n")
+//! }
+//!
+//! let mut tx = ByteDeque::<16>::new();
+//! send(&mut tx).unwrap();
+//! assert_eq!(BufRead::fill_buf(&mut tx).unwrap(), b"AT+GMR\r\n");
+//!
+//! let mut buf = [0; 16];
+//! assert_eq!(Read::read(&mut tx, &mut buf).unwrap(), 8);
+//! assert_eq!(Read::read(&mut tx, &mut buf).unwrap(), 0);
+//! ```
+
+use embedded_io::{BufRead, ErrorKind, ErrorType, Read, ReadReady, Write, WriteReady};
+
+use crate::{BareMetalDeque, Consumer, Producer};
+
+impl<const N: usize> ErrorType for BareMetalDeque<u8, N> {
+    type Error = ErrorKind;
+}
+
+impl<const N: usize> Read for BareMetalDeque<u8, N> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, ErrorKind> {
+        Ok(BareMetalDeque::read(self, buf))
+    }
+}
+
+impl<const N: usize> BufRead for BareMetalDeque<u8, N> {
+    /// Returns the bytes up to the point where the contents wrap around the ring.
+    fn fill_buf(&mut self) -> Result<&[u8], ErrorKind> {
+        Ok(self.as_slices().0)
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.discard(amt.min(self.len()));
+    }
+}
+
+impl<const N: usize> ReadReady for BareMetalDeque<u8, N> {
+    fn read_ready(&mut self) -> Result<bool, ErrorKind> {
+        Ok(!self.is_empty())
+    }
+}
+
+impl<const N: usize> Write for BareMetalDeque<u8, N> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, ErrorKind> {
+        match BareMetalDeque::write(self, buf) {
+            0 if !buf.is_empty() => Err(ErrorKind::WriteZero),
+            n => Ok(n),
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), ErrorKind> {
+        Ok(())
+    }
+}
+
+impl<const N: usize> WriteReady for BareMetalDeque<u8, N> {
+    fn write_ready(&mut self) -> Result<bool, ErrorKind> {
+        Ok(!self.is_full())
+    }
+}
+
+impl<const N: usize> ErrorType for Producer<'_, u8, N> {
+    type Error = ErrorKind;
+}
+
+impl<const N: usize> Write for Producer<'_, u8, N> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, ErrorKind> {
+        match buf.iter().take_while(|b| self.push(**b).is_ok()).count() {
+            0 if !buf.is_empty() => Err(ErrorKind::WriteZero),
+            n => Ok(n),
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), ErrorKind> {
+        Ok(())
+    }
+}
+
+impl<const N: usize> WriteReady for Producer<'_, u8, N> {
+    fn write_ready(&mut self) -> Result<bool, ErrorKind> {
+        Ok(!self.is_full())
+    }
+}
+
+impl<const N: usize> ErrorType for Consumer<'_, u8, N> {
+    type Error = ErrorKind;
+}
+
+impl<const N: usize> Read for Consumer<'_, u8, N> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, ErrorKind> {
+        let mut n = 0;
+        while let Some(slot) = buf.get_mut(n)
+            && let Some(b) = self.pop()
+        {
+            *slot = b;
+            n += 1;
+        }
+        Ok(n)
+    }
+}
+
+impl<const N: usize> ReadReady for Consumer<'_, u8, N> {
+    fn read_ready(&mut self) -> Result<bool, ErrorKind> {
+        Ok(!self.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ByteDeque;
+
+    #[test]
+    fn wrapped_buf_read_test() {
+        let mut q = ByteDeque::<4>::new();
+        assert_eq!(Write::write(&mut q, b"abc"), Ok(3));
+        assert_eq!(Read::read(&mut q, &mut [0; 2]), Ok(2));
+        assert_eq!(Write::write(&mut q, b"def"), Ok(3));
+        assert_eq!(Write::write(&mut q, b"g"), Err(ErrorKind::WriteZero));
+        assert_eq!(Write::write(&mut q, b""), Ok(0));
+        assert!(!q.write_ready().unwrap());
+
+        assert_eq!(q.fill_buf().unwrap(), b"cd");
+        q.consume(2);
+        assert_eq!(q.fill_buf().unwrap(), b"ef");
+        q.consume(10);
+        assert!(q.is_empty());
+        assert!(!q.read_ready().unwrap());
+        assert_eq!(q.fill_buf().unwrap(), b"");
+    }
+
+    #[test]
+    fn spsc_halves_test() {
+        let mut queue = crate::SpscQueue::<u8, 4>::new();
+        let (mut tx, mut rx) = queue.split();
+        assert_eq!(Write::write(&mut tx, b"abcdef"), Ok(4));
+        assert_eq!(Write::write(&mut tx, b"g"), Err(ErrorKind::WriteZero));
+        assert!(!tx.write_ready().unwrap());
+
+        let mut buf = [0; 3];
+        assert_eq!(Read::read(&mut rx, &mut buf), Ok(3));
+        assert_eq!(&buf, b"abc");
+        assert_eq!(Read::read(&mut rx, &mut buf), Ok(1));
+        assert_eq!(Read::read(&mut rx, &mut buf), Ok(0));
+        assert!(!rx.read_ready().unwrap());
+    }
+}
@@ -0,0 +1,173 @@
+//! A ring of recent events that survives a soft reset, for crash forensics that a
+//! bootloader or the next boot can dump.
+//!
+//! The recorder must live in RAM that startup code does not zero or initialize, typically
+//! a `static` placed in a `.uninit` linker section, declared as a `MaybeUninit` so no
+//! initializer runs. [`FlightRecorder::from_uninit`] then checks the memory's header
+//! words as plain integers, keeps the events if they are intact, and otherwise
+//! initializes an empty recorder, so no separate zeroing step is needed. Events must be
+//! [`AnyBits`] types, so leftover or garbage event data is never an invalid value.
+//!
+//! ```no_run
+//! use core::mem::MaybeUninit;
+//! use core::ptr::addr_of_mut;
+//! use bare_metal_deque::FlightRecorder;
+//!
+//! #[unsafe(link_section = ".uninit.flight")]
+//! static mut FLIGHT: MaybeUninit<FlightRecorder<u32, 64>> = MaybeUninit::uninit();
+//!
+//! // Called once at startup, so this is the only reference to FLIGHT.
+//! let flight = FlightRecorder::from_uninit(unsafe { &mut *addr_of_mut!(FLIGHT) });
+//! let survivors = flight.events().len();
+//! ```
+//!
+//! ```
+//! use bare_metal_deque::FlightRecorder;
+//!
+//! let mut flight = FlightRecorder::<u32, 4>::new();
+//! for event in 1..=5 {
+//!     flight.record(event);
+//! }
+//! // After a soft reset:
+//! assert_eq!(flight.recover(), 4);
+//! assert_eq!(flight.events().view(..), [2, 3, 4, 5]);
+//! ```
+
+use core::mem::MaybeUninit;
+
+use crate::BareMetalDeque;
+
+const MAGIC: u32 = 0x464C_4954;
+
+/// Event types for which every bit pattern is a valid value, so events left in memory by
+/// a previous boot, or garbage after a cold boot, can be read without undefined behavior.
+///
+/// # Safety
+///
+/// Implementors must have no padding and no invalid bit patterns, which rules out `bool`,
+/// `char`, references and most enums.
+pub unsafe trait AnyBits: Copy + Default {}
+
+macro_rules! impl_any_bits {
+    ($($t:ty),*) => {
+        $(unsafe impl AnyBits for $t {})*
+    };
+}
+
+impl_any_bits!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+unsafe impl<T: AnyBits, const N: usize> AnyBits for [T; N] where [T; N]: Default {}
+
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct FlightRecorder<T: Default, const MAX_STORED: usize> {
+    magic: u32,
+    events: BareMetalDeque<T, MAX_STORED>,
+}
+
+impl<T: AnyBits, const MAX_STORED: usize> Default for FlightRecorder<T, MAX_STORED> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: AnyBits, const MAX_STORED: usize> FlightRecorder<T, MAX_STORED> {
+    /// Creates an empty recorder, for when the memory is initialized normally.
+    pub fn new() -> Self {
+        Self { magic: MAGIC, events: BareMetalDeque::new() }
+    }
+
+    /// Takes a recorder in memory that was not initialized at startup, keeping the events
+    /// from before a reset if its header is intact and initializing it empty otherwise.
+    pub fn from_uninit(slot: &'static mut MaybeUninit<Self>) -> &'static mut Self {
+        let ptr = slot.as_mut_ptr();
+        // SAFETY: the header fields are plain integers, read through raw pointers without
+        // creating a reference to a possibly invalid recorder. Like all code using
+        // `.uninit` RAM, this assumes the memory holds some fixed bit pattern, left by the
+        // previous boot or by power-up, rather than bytes Rust considers uninitialized.
+        // Volatile reads only keep the compiler from merging or eliding the reads.
+        let (magic, start, size) = unsafe {
+            (
+                (&raw const (*ptr).magic).read_volatile(),
+                (&raw const (*ptr).events.start).read_volatile(),
+                (&raw const (*ptr).events.size).read_volatile(),
+            )
+        };
+        if magic != MAGIC || BareMetalDeque::<T, MAX_STORED>::check_raw_invariants(start, size).is_err() {
+            return slot.write(Self::new());
+        }
+        // SAFETY: under the same assumption, the header is valid, and every other field,
+        // the events included, is an integer or an `AnyBits` type, for which any bit
+        // pattern is a valid value.
+        unsafe { slot.assume_init_mut() }
+    }
+
+    /// Validates a recorder that may have been corrupted, returning the number of events
+    /// kept. If it is not intact, resets it to empty and returns 0.
+    pub fn recover(&mut self) -> usize {
+        if self.magic != MAGIC || self.events.check_invariants().is_err() {
+            *self = Self::new();
+        }
+        self.events.len()
+    }
+
+    /// Appends `event`, overwriting the oldest one if the recorder is full.
+    pub fn record(&mut self, event: T) {
+        if self.events.is_full() {
+            self.events.pop_front();
+        }
+        let _ = self.events.try_push_back(event);
+    }
+
+    /// The recorded events, oldest first.
+    pub fn events(&self) -> &BareMetalDeque<T, MAX_STORED> {
+        &self.events
+    }
+
+    /// Removes all events, as after they have been dumped.
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recover_test() {
+        let mut flight = FlightRecorder::<u16, 3>::new();
+        flight.record(7);
+        flight.magic ^= 1;
+        assert_eq!(flight.recover(), 0);
+
+        flight.record(8);
+        flight.events.start = 5;
+        assert_eq!(flight.recover(), 0);
+
+        flight.record(9);
+        assert_eq!(flight.recover(), 1);
+        assert_eq!(flight.events().front(), Some(9));
+        assert_eq!(FlightRecorder::<u16, 0>::new().recover(), 0);
+    }
+
+    #[test]
+    fn from_uninit_test() {
+        type Recorder = FlightRecorder<u16, 3>;
+        let mut before_reset = Recorder::new();
+        before_reset.record(4);
+        before_reset.record(5);
+        let slot = Box::leak(Box::new(MaybeUninit::new(before_reset)));
+        assert_eq!(FlightRecorder::from_uninit(slot).events().view(..), [4, 5]);
+
+        let garbage = Box::leak(Box::new(MaybeUninit::<Recorder>::uninit()));
+        unsafe {
+            garbage.as_mut_ptr().cast::<u8>().write_bytes(0xA5, size_of::<Recorder>());
+            (&raw mut (*garbage.as_mut_ptr()).magic).write(MAGIC);
+        }
+        let flight = FlightRecorder::from_uninit(garbage);
+        assert!(flight.events().is_empty());
+        flight.record(6);
+        assert_eq!(flight.recover(), 1);
+    }
+}
@@ -0,0 +1,304 @@
+//! A queue of variable-length byte messages sharing one backing ring. Each frame is
+//! stored behind a two-byte little-endian length header, so a short message costs
+//! only its own length plus two bytes instead of a full fixed-size slot.
+//!
+//! ```
+//! use bare_metal_deque::FrameQueue;
+//!
+//! let mut q = FrameQueue::<32>::new();
+//! q.push_frame(b"ping").unwrap();
+//! q.push_frame(b"status?").unwrap();
+//! assert_eq!(q.len(), 2);
+//!
+//! let mut buf = [0; 16];
+//! assert_eq!(q.pop_frame(&mut buf), Some(4));
+//! assert_eq!(&buf[..4], b"ping");
+//! assert_eq!(q.peek_frame_len(), Some(7));
+//! ```
+//!
+//! A [`CrcFrameQueue`] additionally appends a checksum to every frame and verifies it
+//! on the way out, for rings kept in battery-backed RAM or shared with another
+//! processor:
+//! ```
+//! use bare_metal_deque::{CrcFrameQueue, crc::Crc16};
+//!
+//! let mut q = CrcFrameQueue::<Crc16, 32>::new();
+//! q.push_frame(b"boot").unwrap();
+//! let mut buf = [0; 8];
+//! assert_eq!(q.pop_frame(&mut buf), Some(4));
+//! assert_eq!(q.corrupt_frames(), 0);
+//! ```
+
+use core::marker::PhantomData;
+
+use crate::ByteDeque;
+use crate::crc::Crc;
+
+const HEADER_LEN: usize = 2;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FrameError {
+    /// The queue cannot hold the frame and its header.
+    NoSpace,
+    /// The frame is longer than a header can describe.
+    TooLong,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct FrameQueue<const N: usize> {
+    bytes: ByteDeque<N>,
+    frames: usize,
+}
+
+impl<const N: usize> Default for FrameQueue<N> {
+    fn default() -> Self {
+        Self { bytes: ByteDeque::new(), frames: 0 }
+    }
+}
+
+impl<const N: usize> FrameQueue<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of queued frames.
+    pub fn len(&self) -> usize {
+        self.frames
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames == 0
+    }
+
+    /// Number of backing bytes in use, headers included.
+    pub fn bytes_used(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Whether a frame of `len` bytes would currently fit.
+    pub fn fits(&self, len: usize) -> bool {
+        len <= u16::MAX as usize && len + HEADER_LEN <= N - self.bytes.len()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Appends `frame` as a single message. Nothing is written on failure.
+    pub fn push_frame(&mut self, frame: &[u8]) -> Result<(), FrameError> {
+        if frame.len() > u16::MAX as usize {
+            return Err(FrameError::TooLong);
+        }
+        if !self.fits(frame.len()) {
+            return Err(FrameError::NoSpace);
+        }
+        self.bytes.write(&(frame.len() as u16).to_le_bytes());
+        self.bytes.write(frame);
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// Length of the oldest frame, if any.
+    pub fn peek_frame_len(&self) -> Option<usize> {
+        if self.frames == 0 {
+            return None;
+        }
+        Some(u16::from_le_bytes([self.bytes[0], self.bytes[1]]) as usize)
+    }
+
+    /// Removes the oldest frame and copies it into `dst`, returning its length. Returns
+    /// `None`, leaving the frame queued, if there is no frame or it does not fit in `dst`;
+    /// `peek_frame_len()` gives the length needed.
+    pub fn pop_frame(&mut self, dst: &mut [u8]) -> Option<usize> {
+        let len = self.peek_frame_len()?;
+        let dst = dst.get_mut(..len)?;
+        self.bytes.discard(HEADER_LEN);
+        self.bytes.read(dst);
+        self.frames -= 1;
+        Some(len)
+    }
+}
+
+/// A frame queue whose frames carry a checksum computed by `C` over the header and
+/// payload. Frames that fail verification are skipped and counted. If a corrupted
+/// header claims more bytes than are stored, the remaining contents cannot be
+/// delimited and are discarded as a single corrupt frame.
+#[derive(Copy, Clone, Debug)]
+pub struct CrcFrameQueue<C: Crc, const N: usize> {
+    bytes: ByteDeque<N>,
+    frames: usize,
+    corrupt_frames: u32,
+    crc: PhantomData<C>,
+}
+
+impl<C: Crc, const N: usize> Default for CrcFrameQueue<C, N> {
+    fn default() -> Self {
+        Self { bytes: ByteDeque::new(), frames: 0, corrupt_frames: 0, crc: PhantomData }
+    }
+}
+
+impl<C: Crc, const N: usize> CrcFrameQueue<C, N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of queued frames, including any not yet found to be corrupt.
+    pub fn len(&self) -> usize {
+        self.frames
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Number of backing bytes in use, headers and checksums included.
+    pub fn bytes_used(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Number of frames discarded because they failed verification.
+    pub fn corrupt_frames(&self) -> u32 {
+        self.corrupt_frames
+    }
+
+    /// Whether a frame of `len` bytes would currently fit.
+    pub fn fits(&self, len: usize) -> bool {
+        len <= u16::MAX as usize && len + HEADER_LEN + C::LEN <= N - self.bytes.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.bytes = ByteDeque::new();
+        self.frames = 0;
+    }
+
+    /// Length of the oldest frame, if any, as its header states. The frame is not
+    /// verified until it is popped.
+    pub fn peek_frame_len(&self) -> Option<usize> {
+        if self.bytes.len() < HEADER_LEN {
+            return None;
+        }
+        Some(u16::from_le_bytes([self.bytes[0], self.bytes[1]]) as usize)
+    }
+
+    /// Appends `frame` followed by its checksum. Nothing is written on failure.
+    pub fn push_frame(&mut self, frame: &[u8]) -> Result<(), FrameError> {
+        if frame.len() > u16::MAX as usize {
+            return Err(FrameError::TooLong);
+        }
+        if !self.fits(frame.len()) {
+            return Err(FrameError::NoSpace);
+        }
+        let header = (frame.len() as u16).to_le_bytes();
+        let checksum = C::checksum(header.iter().chain(frame.iter()).copied()).to_le_bytes();
+        self.bytes.write(&header);
+        self.bytes.write(frame);
+        self.bytes.write(&checksum[..C::LEN]);
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// Removes the oldest intact frame and copies it into `dst`, returning its length.
+    /// Corrupt frames in front of it are discarded. Returns `None`, leaving the intact
+    /// frame queued, if it does not fit in `dst`; `peek_frame_len()` then gives the
+    /// length needed.
+    pub fn pop_frame(&mut self, dst: &mut [u8]) -> Option<usize> {
+        while let Some(len) = self.peek_frame_len() {
+            let stored = HEADER_LEN + len + C::LEN;
+            if stored > self.bytes.len() {
+                self.corrupt_frames += 1;
+                self.clear();
+                return None;
+            }
+
+            let checked = HEADER_LEN + len;
+            let expected = C::checksum(self.bytes.iter().take(checked).copied()).to_le_bytes();
+            if !(0..C::LEN).all(|i| self.bytes[checked + i] == expected[i]) {
+                self.corrupt_frames += 1;
+                self.bytes.discard(stored);
+                self.frames = self.frames.saturating_sub(1);
+                continue;
+            }
+
+            let dst = dst.get_mut(..len)?;
+            self.bytes.discard(HEADER_LEN);
+            self.bytes.read(dst);
+            self.bytes.discard(C::LEN);
+            self.frames = self.frames.saturating_sub(1);
+            return Some(len);
+        }
+        if !self.bytes.is_empty() {
+            self.corrupt_frames += 1;
+            self.clear();
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crc::{Crc16, Crc32};
+
+    #[test]
+    fn frame_queue_test() {
+        let mut q = FrameQueue::<16>::new();
+        let mut buf = [0; 8];
+        assert_eq!(q.pop_frame(&mut buf), None);
+        for round in 0..5u8 {
+            q.push_frame(&[round; 5]).unwrap();
+            q.push_frame(&[]).unwrap();
+            q.push_frame(&[round + 1; 3]).unwrap();
+            assert_eq!(q.bytes_used(), 14);
+            assert_eq!(q.push_frame(&[1]), Err(FrameError::NoSpace));
+
+            assert_eq!(q.pop_frame(&mut buf), Some(5));
+            assert_eq!(&buf[..5], &[round; 5]);
+            assert_eq!(q.pop_frame(&mut buf), Some(0));
+            assert_eq!(q.pop_frame(&mut buf[..2]), None);
+            assert_eq!(q.peek_frame_len(), Some(3));
+            assert_eq!(q.pop_frame(&mut buf[..3]), Some(3));
+            assert_eq!(&buf[..3], &[round + 1; 3]);
+            assert!(q.is_empty());
+            assert_eq!(q.bytes_used(), 0);
+        }
+    }
+
+    #[test]
+    fn crc_frame_queue_test() {
+        let mut q = CrcFrameQueue::<Crc32, 32>::new();
+        q.push_frame(b"one").unwrap();
+        q.push_frame(b"two").unwrap();
+        q.push_frame(b"three").unwrap();
+        assert_eq!(q.bytes_used(), 9 + 9 + 11);
+        assert_eq!(q.push_frame(b"four"), Err(FrameError::NoSpace));
+        assert_eq!(q.len(), 3);
+
+        // Flip a payload bit in the second frame.
+        q.bytes[9 + 2] ^= 0x01;
+        let mut buf = [0; 8];
+        assert_eq!(q.pop_frame(&mut buf), Some(3));
+        assert_eq!(&buf[..3], b"one");
+        assert_eq!(q.pop_frame(&mut buf[..4]), None);
+        assert_eq!((q.len(), q.peek_frame_len()), (1, Some(5)));
+        assert_eq!(q.pop_frame(&mut buf), Some(5));
+        assert_eq!(&buf[..5], b"three");
+        assert_eq!(q.corrupt_frames(), 1);
+        assert_eq!(q.pop_frame(&mut buf), None);
+        assert!(q.is_empty());
+    }
+
+    #[test]
+    fn corrupt_header_test() {
+        let mut q = CrcFrameQueue::<Crc16, 32>::new();
+        q.push_frame(b"abc").unwrap();
+        q.push_frame(b"def").unwrap();
+        q.bytes[1] = 0xFF;
+        let mut buf = [0; 8];
+        assert_eq!(q.pop_frame(&mut buf), None);
+        assert_eq!(q.corrupt_frames(), 1);
+        assert!(q.is_empty());
+
+        q.push_frame(b"ghi").unwrap();
+        assert_eq!(q.pop_frame(&mut buf), Some(3));
+    }
+}
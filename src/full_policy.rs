@@ -0,0 +1,123 @@
+//! A deque whose behavior when full is chosen at runtime, for products where a setting,
+//! such as a lossless logging mode, must switch between dropping new data, dropping old
+//! data, and treating overflow as a fault.
+//!
+//! ```
+//! use bare_metal_deque::{FullPolicy, PolicyDeque};
+//!
+//! let mut log = PolicyDeque::<u8, 2>::new(FullPolicy::OverwriteOldest);
+//! log.push(1).unwrap();
+//! log.push(2).unwrap();
+//! assert_eq!(log.push(3), Ok(Some(1)));
+//!
+//! log.set_full_policy(FullPolicy::Reject);
+//! assert!(log.push(4).is_err());
+//! assert_eq!(log.deque().view(..), [2, 3]);
+//! ```
+
+use crate::{BareMetalDeque, CapacityError};
+
+/// What a push onto a full [`PolicyDeque`] does.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum FullPolicy {
+    /// Drop the new element and report an error.
+    #[default]
+    Reject,
+    /// Drop the oldest element to make room.
+    OverwriteOldest,
+    /// Panic, for configurations where losing data is a fault.
+    Panic,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct PolicyDeque<T: Default, const MAX_STORED: usize> {
+    deque: BareMetalDeque<T, MAX_STORED>,
+    policy: FullPolicy,
+}
+
+impl<T: Copy + Default, const MAX_STORED: usize> Default for PolicyDeque<T, MAX_STORED> {
+    fn default() -> Self {
+        Self::new(FullPolicy::default())
+    }
+}
+
+impl<T: Copy + Default, const MAX_STORED: usize> PolicyDeque<T, MAX_STORED> {
+    pub fn new(policy: FullPolicy) -> Self {
+        Self { deque: BareMetalDeque::new(), policy }
+    }
+
+    pub fn full_policy(&self) -> FullPolicy {
+        self.policy
+    }
+
+    /// Changes the policy for later pushes. Elements already held are kept.
+    pub fn set_full_policy(&mut self, policy: FullPolicy) {
+        self.policy = policy;
+    }
+
+    /// Appends `value`, returning the element overwritten to make room, if any. When full,
+    /// fails under `Reject` and panics under `Panic`.
+    pub fn push(&mut self, value: T) -> Result<Option<T>, CapacityError> {
+        self.deque.push_back_with_policy(value, self.policy)
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.deque.pop_front()
+    }
+
+    pub fn len(&self) -> usize {
+        self.deque.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.deque.is_empty()
+    }
+
+    /// The underlying deque, for reading its contents.
+    pub fn deque(&self) -> &BareMetalDeque<T, MAX_STORED> {
+        &self.deque
+    }
+}
+
+impl<T: Copy + Default, const MAX_STORED: usize> BareMetalDeque<T, MAX_STORED> {
+    /// Appends `value`, handling a full deque according to `policy`, as `PolicyDeque::push`.
+    pub(crate) fn push_back_with_policy(&mut self, value: T, policy: FullPolicy) -> Result<Option<T>, CapacityError> {
+        if !self.is_full() {
+            self.push_back(value);
+            return Ok(None);
+        }
+        match policy {
+            FullPolicy::Reject => self.try_push_back(value).map(|()| None),
+            FullPolicy::Panic => panic!("Deque is full"),
+            FullPolicy::OverwriteOldest => {
+                let Some(oldest) = self.pop_front() else {
+                    return Err(CapacityError);
+                };
+                self.record_evictions(1);
+                self.push_back(value);
+                Ok(Some(oldest))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policy_test() {
+        let mut q = PolicyDeque::<u8, 1>::default();
+        assert_eq!(q.full_policy(), FullPolicy::Reject);
+        assert_eq!(q.push(1), Ok(None));
+        assert_eq!(q.push(2), Err(CapacityError));
+        q.set_full_policy(FullPolicy::OverwriteOldest);
+        assert_eq!(q.push(3), Ok(Some(1)));
+        assert_eq!(PolicyDeque::<u8, 0>::new(FullPolicy::OverwriteOldest).push(1), Err(CapacityError));
+
+        q.set_full_policy(FullPolicy::Panic);
+        assert_eq!(q.pop_front(), Some(3));
+        assert_eq!(q.push(4), Ok(None));
+        assert!(std::panic::catch_unwind(move || q.push(5)).is_err());
+    }
+}
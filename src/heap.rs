@@ -0,0 +1,95 @@
+//! Binary max-heap operations over the logical contents, for code that mostly treats the
+//! deque as a FIFO but occasionally needs the largest element. Wrap elements in
+//! [`core::cmp::Reverse`] for a min-heap.
+//!
+//! ```
+//! use bare_metal_deque::deque;
+//!
+//! let mut jobs = deque![3, 9, 4; cap = 8];
+//! jobs.make_heap();
+//! jobs.push_heap(7);
+//! assert_eq!(jobs.pop_heap(), Some(9));
+//! assert_eq!(jobs.pop_heap(), Some(7));
+//! assert_eq!(jobs.len(), 2);
+//! ```
+
+use crate::BareMetalDeque;
+
+impl<T: Copy + Clone + Default + Ord, const MAX_STORED: usize> BareMetalDeque<T, MAX_STORED> {
+    /// Reorders the contents into a max-heap, with the largest element at the front.
+    pub fn make_heap(&mut self) {
+        for i in (0..self.size / 2).rev() {
+            self.sift_down(i);
+        }
+    }
+
+    /// Pushes `value` onto a deque already arranged by `make_heap()`, keeping it a heap.
+    /// Panics if the deque is full.
+    pub fn push_heap(&mut self, value: T) {
+        self.push_back(value);
+        let mut i = self.size - 1;
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if self.heap_at(parent) >= self.heap_at(i) {
+                break;
+            }
+            self.heap_swap(parent, i);
+            i = parent;
+        }
+    }
+
+    /// Removes and returns the largest element of a deque arranged by `make_heap()`,
+    /// keeping the rest a heap.
+    pub fn pop_heap(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        self.heap_swap(0, self.size - 1);
+        let top = self.pop_back();
+        self.sift_down(0);
+        top
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let mut largest = i;
+            for child in [2 * i + 1, 2 * i + 2] {
+                if child < self.size && self.heap_at(child) > self.heap_at(largest) {
+                    largest = child;
+                }
+            }
+            if largest == i {
+                return;
+            }
+            self.heap_swap(i, largest);
+            i = largest;
+        }
+    }
+
+    fn heap_at(&self, i: usize) -> T {
+        self.array[self.physical(i)]
+    }
+
+    fn heap_swap(&mut self, i: usize, j: usize) {
+        let (a, b) = (self.physical(i), self.physical(j));
+        self.array.swap(a, b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heap_sort_test() {
+        let mut q = BareMetalDeque::<i32, 8>::new();
+        q.extend_from_slice(&[0; 5]);
+        q.discard(5);
+        q.extend_from_slice(&[4, -1, 7, 3, 7, 0]);
+        q.make_heap();
+        q.push_heap(5);
+        q.push_heap(-2);
+        let sorted: Vec<_> = core::iter::from_fn(|| q.pop_heap()).collect();
+        assert_eq!(sorted, vec![7, 7, 5, 4, 3, 0, -1, -2]);
+    }
+}
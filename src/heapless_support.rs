@@ -0,0 +1,83 @@
+//! Conversions between `BareMetalDeque` and [`heapless`](https://docs.rs/heapless)'s
+//! `Deque` and `Vec`, behind the `heapless` feature. Since the capacities on either side
+//! may differ, every conversion is a `TryFrom` that fails with [`CapacityError`] when the
+//! contents do not fit.
+//!
+//! ```
+//! use bare_metal_deque::BareMetalDeque;
+//!
+//! let mut q = BareMetalDeque::<u8, 4>::new();
+//! q.push_back(1);
+//! q.push_front(0);
+//!
+//! let v = heapless::Vec::<u8, 8>::try_from(q).unwrap();
+//! assert_eq!(v.as_slice(), &[0, 1]);
+//! let back = BareMetalDeque::<u8, 2>::try_from(v).unwrap();
+//! assert_eq!(back.len(), 2);
+//! assert!(heapless::Deque::<u8, 1>::try_from(back).is_err());
+//! ```
+
+use crate::{BareMetalDeque, CapacityError};
+
+impl<T: Copy + Default, const N: usize, const M: usize> TryFrom<BareMetalDeque<T, N>> for heapless::Deque<T, M> {
+    type Error = CapacityError;
+
+    fn try_from(deque: BareMetalDeque<T, N>) -> Result<Self, CapacityError> {
+        let mut result = heapless::Deque::new();
+        for value in deque.iter() {
+            result.push_back(*value).map_err(|_| CapacityError)?;
+        }
+        Ok(result)
+    }
+}
+
+impl<T: Copy + Default, const N: usize, const M: usize> TryFrom<BareMetalDeque<T, N>> for heapless::Vec<T, M> {
+    type Error = CapacityError;
+
+    fn try_from(deque: BareMetalDeque<T, N>) -> Result<Self, CapacityError> {
+        let mut result = heapless::Vec::new();
+        for value in deque.iter() {
+            result.push(*value).map_err(|_| CapacityError)?;
+        }
+        Ok(result)
+    }
+}
+
+impl<T: Copy + Default, const N: usize, const M: usize> TryFrom<heapless::Deque<T, M>> for BareMetalDeque<T, N> {
+    type Error = CapacityError;
+
+    fn try_from(deque: heapless::Deque<T, M>) -> Result<Self, CapacityError> {
+        Self::try_from_exact(deque.iter().copied())
+    }
+}
+
+impl<T: Copy + Default, const N: usize, const M: usize> TryFrom<heapless::Vec<T, M>> for BareMetalDeque<T, N> {
+    type Error = CapacityError;
+
+    fn try_from(vec: heapless::Vec<T, M>) -> Result<Self, CapacityError> {
+        Self::try_from_exact(vec.iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deque_round_trip_test() {
+        let mut q = BareMetalDeque::<i32, 3>::new();
+        for x in [1, 2, 3] {
+            q.push_back(x);
+        }
+        q.pop_front();
+        q.push_back(4);
+
+        let h = heapless::Deque::<i32, 3>::try_from(q).unwrap();
+        assert_eq!(h.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert!(heapless::Deque::<i32, 2>::try_from(q).is_err());
+
+        let back = BareMetalDeque::<i32, 5>::try_from(h.clone()).unwrap();
+        assert_eq!(back.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!(BareMetalDeque::<i32, 2>::try_from(h).unwrap_err(), CapacityError);
+    }
+}
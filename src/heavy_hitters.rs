@@ -0,0 +1,114 @@
+//! Frequency counts of the most common items among the last `WINDOW` seen, in fixed
+//! memory, for spotting the chattiest peers or the most frequent error codes.
+//!
+//! Up to `SLOTS` distinct items are counted at once. When a new item arrives and every
+//! slot is taken, it replaces the least-counted item and inherits that count, as in the
+//! space-saving algorithm. Counts are therefore upper bounds, and are exact whenever the
+//! window holds no more than `SLOTS` distinct items. Items leaving the window are
+//! subtracted from their counts.
+//!
+//! ```
+//! use bare_metal_deque::HeavyHitters;
+//!
+//! let mut peers = HeavyHitters::<u8, 8, 2>::new();
+//! for peer in [7, 3, 7, 7, 9, 7] {
+//!     peers.push(peer);
+//! }
+//! assert_eq!(peers.top(), Some((7, 4)));
+//! ```
+
+use crate::BareMetalDeque;
+
+#[derive(Copy, Clone, Debug)]
+pub struct HeavyHitters<T: Default, const WINDOW: usize, const SLOTS: usize> {
+    window: BareMetalDeque<T, WINDOW>,
+    slots: [Option<(T, u32)>; SLOTS],
+}
+
+impl<T: Copy + Default + PartialEq, const WINDOW: usize, const SLOTS: usize> Default for HeavyHitters<T, WINDOW, SLOTS> {
+    fn default() -> Self {
+        Self { window: BareMetalDeque::new(), slots: [None; SLOTS] }
+    }
+}
+
+impl<T: Copy + Default + PartialEq, const WINDOW: usize, const SLOTS: usize> HeavyHitters<T, WINDOW, SLOTS> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records an occurrence of `item`, forgetting the oldest occurrence once `WINDOW`
+    /// are held.
+    pub fn push(&mut self, item: T) {
+        if WINDOW == 0 || SLOTS == 0 {
+            return;
+        }
+        if self.window.is_full()
+            && let Some(oldest) = self.window.pop_front()
+            && let Some(slot) = self.slot_of(oldest)
+        {
+            match &mut self.slots[slot] {
+                Some((_, 1)) => self.slots[slot] = None,
+                Some((_, count)) => *count -= 1,
+                None => {}
+            }
+        }
+        self.window.push_back(item);
+
+        if let Some(slot) = self.slot_of(item) {
+            if let Some((_, count)) = &mut self.slots[slot] {
+                *count += 1;
+            }
+        } else if let Some(free) = self.slots.iter().position(Option::is_none) {
+            self.slots[free] = Some((item, 1));
+        } else {
+            let (slot, min) = self.slots.iter().enumerate()
+                .filter_map(|(i, s)| s.map(|(_, count)| (i, count)))
+                .min_by_key(|(_, count)| *count)
+                .unwrap();
+            self.slots[slot] = Some((item, min + 1));
+        }
+    }
+
+    /// The counted items and their counts, in no particular order.
+    pub fn counts(&self) -> impl Iterator<Item = (T, u32)> + '_ {
+        self.slots.iter().flatten().copied()
+    }
+
+    /// The item with the highest count, or `None` if nothing has been recorded.
+    pub fn top(&self) -> Option<(T, u32)> {
+        self.counts().max_by_key(|(_, count)| *count)
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    fn slot_of(&self, item: T) -> Option<usize> {
+        self.slots.iter().position(|s| matches!(s, Some((tracked, _)) if *tracked == item))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sliding_counts_test() {
+        let mut hh = HeavyHitters::<char, 4, 3>::new();
+        for c in "aabc".chars() {
+            hh.push(c);
+        }
+        let mut counts: Vec<_> = hh.counts().collect();
+        counts.sort();
+        assert_eq!(counts, vec![('a', 2), ('b', 1), ('c', 1)]);
+
+        // The window slides past both a's; d takes over a least-counted slot.
+        hh.push('d');
+        hh.push('d');
+        assert_eq!(hh.top(), Some(('d', 3)));
+        assert!(hh.counts().all(|(c, _)| c != 'a'));
+
+        hh.clear();
+        assert_eq!(hh.top(), None);
+    }
+}
@@ -0,0 +1,120 @@
+//! A fixed-bin histogram for tracking value distributions (latencies, ADC readings, etc.)
+//! without storing the individual samples.
+//!
+//! ```
+//! use bare_metal_deque::Histogram;
+//!
+//! let mut h = Histogram::<4>::new([10, 20, 30, 40]);
+//! for v in [5, 15, 15, 25, 35, 99] {
+//!     h.record(v);
+//! }
+//! assert_eq!(h.total(), 6);
+//! assert_eq!(h.overflow(), 1);
+//! assert_eq!(h.percentile(50), Some(20));
+//! assert_eq!(h.bins().map(|(_, count)| count).sum::<u32>(), 5);
+//! ```
+
+/// Counts recorded values into `BINS` buckets. Bin `i` holds values greater than
+/// the upper bound of bin `i - 1` and less than or equal to its own upper bound.
+/// Values above the last upper bound are counted separately as overflow.
+#[derive(Copy, Clone, Debug)]
+pub struct Histogram<const BINS: usize> {
+    upper_bounds: [u32; BINS],
+    counts: [u32; BINS],
+    overflow: u32,
+}
+
+impl<const BINS: usize> Histogram<BINS> {
+    /// Creates a histogram with the given inclusive upper bounds, which must be
+    /// strictly increasing.
+    pub fn new(upper_bounds: [u32; BINS]) -> Self {
+        for i in 1..BINS {
+            if upper_bounds[i - 1] >= upper_bounds[i] {
+                panic!("Histogram bounds must be strictly increasing");
+            }
+        }
+        Self { upper_bounds, counts: [0; BINS], overflow: 0 }
+    }
+
+    /// Creates a histogram whose bins each span `width` values, the first one
+    /// ending at `first_upper`.
+    pub fn linear(first_upper: u32, width: u32) -> Self {
+        let mut upper_bounds = [0; BINS];
+        for (i, bound) in upper_bounds.iter_mut().enumerate() {
+            *bound = first_upper + width * i as u32;
+        }
+        Self::new(upper_bounds)
+    }
+
+    pub fn record(&mut self, value: u32) {
+        match self.upper_bounds.iter().position(|bound| value <= *bound) {
+            Some(i) => self.counts[i] = self.counts[i].saturating_add(1),
+            None => self.overflow = self.overflow.saturating_add(1),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.counts = [0; BINS];
+        self.overflow = 0;
+    }
+
+    /// Number of recorded values, including overflow.
+    pub fn total(&self) -> u32 {
+        self.counts.iter().fold(self.overflow, |total, c| total.saturating_add(*c))
+    }
+
+    pub fn overflow(&self) -> u32 {
+        self.overflow
+    }
+
+    /// Estimates the `p`th percentile (0..=100) as the upper bound of the bin in
+    /// which it falls. Returns `None` if nothing has been recorded or if the
+    /// percentile falls among the overflow values.
+    pub fn percentile(&self, p: u8) -> Option<u32> {
+        let total = self.total() as u64;
+        if total == 0 {
+            return None;
+        }
+        let target = (total * p.min(100) as u64).div_ceil(100).max(1);
+        let mut seen = 0;
+        for (upper, count) in self.bins() {
+            seen += count as u64;
+            if seen >= target {
+                return Some(upper);
+            }
+        }
+        None
+    }
+
+    /// Iterates over `(upper_bound, count)` for each bin in ascending order.
+    pub fn bins(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.upper_bounds.iter().copied().zip(self.counts.iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn histogram_test() {
+        let mut h = Histogram::<5>::linear(9, 10);
+        assert_eq!(h.percentile(50), None);
+        for v in 0..50 {
+            h.record(v);
+        }
+        assert!(h.bins().all(|(_, count)| count == 10));
+        assert_eq!(h.percentile(0), Some(9));
+        assert_eq!(h.percentile(20), Some(9));
+        assert_eq!(h.percentile(21), Some(19));
+        assert_eq!(h.percentile(100), Some(49));
+
+        h.record(50);
+        assert_eq!(h.overflow(), 1);
+        assert_eq!(h.total(), 51);
+        assert_eq!(h.percentile(100), None);
+
+        h.clear();
+        assert_eq!(h.total(), 0);
+    }
+}
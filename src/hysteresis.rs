@@ -0,0 +1,108 @@
+//! Threshold alarms with separate enter and exit levels over analog samples, so that an
+//! alarm on a noisy signal sitting near its limit switches once instead of chattering.
+//!
+//! ```
+//! use bare_metal_deque::{BareMetalDeque, Hysteresis, ThresholdEvent};
+//!
+//! let mut temps = BareMetalDeque::<i16, 16>::new();
+//! let mut overheat = Hysteresis::above(850, 800);
+//! for t in [790, 849, 851, 848, 852, 805] {
+//!     temps.push_back(t);
+//!     temps.poll_hysteresis(&mut overheat);
+//! }
+//! assert!(overheat.is_active());
+//! temps.push_back(799);
+//! assert_eq!(temps.poll_hysteresis(&mut overheat), Some(ThresholdEvent::Exited));
+//! ```
+
+use core::cmp::Ordering;
+
+use crate::BareMetalDeque;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ThresholdEvent {
+    /// The signal reached the enter threshold.
+    Entered,
+    /// After an `Entered` event, the signal reached the exit threshold.
+    Exited,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Hysteresis<T> {
+    enter: T,
+    exit: T,
+    rising: bool,
+    active: bool,
+}
+
+impl<T: Copy + PartialOrd> Hysteresis<T> {
+    /// An alarm that activates when a sample reaches `enter` and clears when a sample
+    /// falls to `exit`. Panics unless `exit < enter`.
+    pub fn above(enter: T, exit: T) -> Self {
+        if exit.partial_cmp(&enter) != Some(Ordering::Less) {
+            panic!("Exit threshold must be below the enter threshold");
+        }
+        Self { enter, exit, rising: true, active: false }
+    }
+
+    /// An alarm that activates when a sample falls to `enter` and clears when a sample
+    /// rises to `exit`. Panics unless `exit > enter`.
+    pub fn below(enter: T, exit: T) -> Self {
+        if exit.partial_cmp(&enter) != Some(Ordering::Greater) {
+            panic!("Exit threshold must be above the enter threshold");
+        }
+        Self { enter, exit, rising: false, active: false }
+    }
+
+    /// Whether the alarm is currently active.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    pub fn reset(&mut self) {
+        self.active = false;
+    }
+
+    /// Adds a sample, reporting a change of state, if any.
+    pub fn push(&mut self, sample: T) -> Option<ThresholdEvent> {
+        if !self.active && self.reached(sample, self.enter) {
+            self.active = true;
+            Some(ThresholdEvent::Entered)
+        } else if self.active && self.reached(self.exit, sample) {
+            self.active = false;
+            Some(ThresholdEvent::Exited)
+        } else {
+            None
+        }
+    }
+
+    /// Whether `a` is at or beyond `b` in the alarm's direction.
+    fn reached(&self, a: T, b: T) -> bool {
+        if self.rising { a >= b } else { a <= b }
+    }
+}
+
+impl<T: Copy + Default + PartialOrd, const MAX_STORED: usize> BareMetalDeque<T, MAX_STORED> {
+    /// Checks the newest sample against `hysteresis`, reporting each change of state once.
+    /// Returns `None` if the deque is empty.
+    pub fn poll_hysteresis(&self, hysteresis: &mut Hysteresis<T>) -> Option<ThresholdEvent> {
+        hysteresis.push(self.back()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chatter_test() {
+        use ThresholdEvent::*;
+        let mut low_battery = Hysteresis::below(3.3f32, 3.5);
+        let events: Vec<_> = [3.6, 3.31, 3.29, 3.32, 3.28, 3.45, 3.5, 3.2]
+            .into_iter().map(|v| low_battery.push(v)).collect();
+        assert_eq!(events, vec![None, None, Some(Entered), None, None, None, Some(Exited), Some(Entered)]);
+        assert!(low_battery.is_active());
+        low_battery.reset();
+        assert!(!low_battery.is_active());
+    }
+}
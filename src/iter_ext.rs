@@ -0,0 +1,102 @@
+//! Iterator adaptors for collecting into deques and for processing streams with a
+//! window of recent items.
+//!
+//! ```
+//! use bare_metal_deque::DequeIteratorExt;
+//!
+//! let q = (1..=3).collect_deque::<4>().unwrap();
+//! assert_eq!(q.back(), Some(3));
+//! assert!((1..=5).collect_deque::<4>().is_err());
+//!
+//! // Three-point moving sums over a stream.
+//! let sums = [1, 2, 3, 4, 5]
+//!     .into_iter()
+//!     .ring_buffered::<3>()
+//!     .map(|window| window.iter().sum::<i32>())
+//!     .collect::<Vec<_>>();
+//! assert_eq!(sums, vec![1, 3, 6, 9, 12]);
+//! ```
+
+use crate::{BareMetalDeque, CapacityError};
+
+/// Extension methods for iterators, imported with `use bare_metal_deque::DequeIteratorExt`.
+pub trait DequeIteratorExt: Iterator + Sized
+where
+    Self::Item: Copy + Clone + Default,
+{
+    /// Collects the items into a deque, failing if there are more than `N`.
+    fn collect_deque<const N: usize>(self) -> Result<BareMetalDeque<Self::Item, N>, CapacityError> {
+        BareMetalDeque::try_init_with(self)
+    }
+
+    /// Adapts the iterator to yield, after each item, a copy of the last `N` items seen
+    /// with the newest at the back. The first `N - 1` windows are partially filled.
+    fn ring_buffered<const N: usize>(self) -> RingBuffered<Self, N> {
+        RingBuffered { iter: self, window: BareMetalDeque::new() }
+    }
+}
+
+impl<I: Iterator> DequeIteratorExt for I where I::Item: Copy + Clone + Default {}
+
+/// Iterator returned by [`DequeIteratorExt::ring_buffered`].
+#[derive(Clone, Debug)]
+pub struct RingBuffered<I: Iterator, const N: usize>
+where
+    I::Item: Default,
+{
+    iter: I,
+    window: BareMetalDeque<I::Item, N>,
+}
+
+impl<I: Iterator, const N: usize> RingBuffered<I, N>
+where
+    I::Item: Copy + Clone + Default,
+{
+    /// The most recent items, as of the last call to `next()`.
+    pub fn window(&self) -> &BareMetalDeque<I::Item, N> {
+        &self.window
+    }
+}
+
+impl<I: Iterator, const N: usize> Iterator for RingBuffered<I, N>
+where
+    I::Item: Copy + Clone + Default,
+{
+    type Item = BareMetalDeque<I::Item, N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.iter.next()?;
+        if N == 0 {
+            return Some(self.window);
+        }
+        if self.window.is_full() {
+            self.window.pop_front();
+        }
+        self.window.push_back(item);
+        Some(self.window)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ring_buffered_test() {
+        let mut windows = b"abcd".iter().copied().ring_buffered::<2>();
+        assert!(windows.window().is_empty());
+        assert_eq!(windows.next().unwrap().iter().copied().collect::<Vec<_>>(), b"a");
+        windows.next();
+        windows.next();
+        assert_eq!(windows.window().iter().copied().collect::<Vec<_>>(), b"bc");
+        assert_eq!(windows.next().unwrap().iter().copied().collect::<Vec<_>>(), b"cd");
+        assert!(windows.next().is_none());
+
+        assert!((0..3).ring_buffered::<0>().all(|w| w.is_empty()));
+        assert_eq!((0..0).collect_deque::<0>().map(|q| q.len()), Ok(0));
+    }
+}
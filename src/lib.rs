@@ -1,6 +1,9 @@
 //! The `BareMetalDeque` represents a fixed-size double-ended queue analogous to [VecDeque](https://doc.rust-lang.org/std/collections/struct.VecDeque.html). It is implemented internally as a ring buffer.
 //! 
-//! If you try to add to a full deque, it will panic.
+//! If you try to add to a full deque, it will panic. The `try_` methods and `get()` report
+//! failure instead. With the `no-panic` feature, release builds check at link time that
+//! these and the other `Option`-returning accessors contain no panicking paths; see
+//! `examples/no_panic_check.rs`.
 //! 
 //! Basic deque operations (push/pop front/back):
 //! ```
@@ -70,32 +73,292 @@
 
 #![cfg_attr(not(test), no_std)]
 
-use core::{default::Default, ops::{Index, IndexMut}};
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
+use core::{default::Default, fmt, mem::MaybeUninit, ops::{Index, IndexMut, RangeBounds}};
+
+pub mod cobs;
+pub mod crc;
+pub mod persistent_ring;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+pub mod slip;
+
+mod aligned;
+#[cfg(feature = "alloc")]
+mod alloc_support;
+#[cfg(feature = "arbitrary")]
+mod arbitrary_support;
+#[cfg(feature = "alloc")]
+mod boxed;
+#[cfg(feature = "embedded-hal-nb")]
+mod buffered_serial;
+#[cfg(feature = "bytemuck")]
+mod bytemuck_support;
+mod bytes;
+#[cfg(feature = "embedded-can")]
+mod can_support;
+mod channels;
+mod chunks;
+#[cfg(feature = "cortex-m")]
+mod cortex_m_support;
+#[cfg(feature = "defmt")]
+mod defmt_format;
+#[cfg(feature = "defmt-logger")]
+mod defmt_ring;
+#[cfg(feature = "embedded-io")]
+mod embedded_io_support;
+mod decimating;
+mod delay_line;
+mod dsp;
+mod edge;
+mod ema;
+mod flight_recorder;
+mod frame_queue;
+mod full_policy;
+mod heap;
+#[cfg(feature = "heapless")]
+mod heapless_support;
+mod heavy_hitters;
+mod histogram;
+mod hysteresis;
+mod iter_ext;
+mod line_editor;
+mod local_channel;
+mod macros;
+mod merge;
+#[cfg(feature = "minicbor")]
+mod minicbor_support;
+mod multi_queue;
+mod panic_capture;
+mod peak;
+#[cfg(feature = "postcard")]
+mod postcard_support;
+mod priority_levels;
+mod quantile;
+mod record_queue;
+mod reservation;
+#[cfg(feature = "rand_core")]
+mod rand_support;
+#[cfg(feature = "log")]
+mod ring_logger;
+mod scanline;
+#[cfg(feature = "serde")]
+mod serde_support;
+#[cfg(feature = "critical-section")]
+mod shared;
+mod slab;
+mod snapshot;
+mod snapshot_ring;
+#[cfg(feature = "stats")]
+mod stats;
+mod sort;
+mod spsc;
+mod timestamped;
+#[cfg(feature = "ufmt")]
+mod ufmt_support;
+mod view;
+mod watermark;
+mod window_sum;
+
+pub use aligned::{A16, A32, A4, A64, A8, Aligned};
+#[cfg(feature = "alloc")]
+pub use boxed::BoxedDeque;
+#[cfg(feature = "embedded-hal-nb")]
+pub use buffered_serial::BufferedSerial;
+#[cfg(feature = "bytemuck")]
+pub use bytemuck_support::{PodRingView, ViewError};
+pub use bytes::{ByteDeque, HexDump, WireInt};
+#[cfg(feature = "embedded-can")]
+pub use can_support::{CanRxQueue, CanTxQueue};
+pub use channels::ChannelView;
+pub use chunks::{ArrayChunks, ArrayWindows};
+#[cfg(feature = "cortex-m")]
+pub use cortex_m_support::InterruptFreeDeque;
+#[cfg(feature = "defmt-logger")]
+pub use defmt_ring::DefmtRing;
+pub use decimating::DecimatingDeque;
+pub use dsp::Saturating;
+pub use edge::{Edge, EdgeDetector};
+pub use ema::Ema;
+pub use flight_recorder::{AnyBits, FlightRecorder};
+pub use frame_queue::{CrcFrameQueue, FrameError, FrameQueue};
+pub use full_policy::{FullPolicy, PolicyDeque};
+pub use heavy_hitters::HeavyHitters;
+pub use histogram::Histogram;
+pub use hysteresis::{Hysteresis, ThresholdEvent};
+pub use iter_ext::{DequeIteratorExt, RingBuffered};
+pub use line_editor::LineEditor;
+pub use local_channel::{Receiver, RecvError, SendError, Sender, channel};
+pub use merge::{merge_iter, merge_iter_by, merge_iter_by_key};
+pub use multi_queue::BareMetalMultiQueue;
+pub use panic_capture::PanicCapture;
+pub use peak::{Detection, Extremum, PeakDetector};
+pub use persistent_ring::{PersistentError, PersistentRing, Storage};
+pub use priority_levels::PriorityLevels;
+pub use quantile::P2Quantile;
+pub use record_queue::RecordQueue;
+pub use reservation::Reservation;
+#[cfg(feature = "log")]
+pub use ring_logger::RingLogger;
+pub use scanline::ScanlineRing;
+#[cfg(feature = "critical-section")]
+pub use shared::SharedDeque;
+pub use slab::{BareMetalSlab, SlabKey};
+pub use snapshot::{SnapshotElement, SnapshotError};
+pub use snapshot_ring::SnapshotRing;
+pub use spsc::{Consumer, Producer, SpscQueue};
+#[cfg(feature = "stats")]
+pub use stats::QueueStats;
+pub use timestamped::TimestampedDeque;
+pub use view::{DequeView, DequeViewMut};
+pub use watermark::{Watermark, WatermarkEvent};
+pub use window_sum::WindowSum;
+
+/// Error returned when contents do not fit in the destination's capacity.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CapacityError;
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("capacity exceeded")
+    }
+}
+
+/// A broken internal invariant, reported by `check_invariants()`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum InvariantError {
+    /// The front index lies outside the backing array.
+    StartOutOfRange,
+    /// The deque claims to hold more elements than its capacity.
+    SizeOutOfRange,
+}
+
+/// Error returned by `try_from_fn()`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TryFromFnError<E> {
+    /// The requested length exceeds the capacity, so no element was generated.
+    Capacity,
+    /// Generating an element failed.
+    Init(E),
+}
+
+// `repr(C)` keeps the array at offset zero, so wrapping the deque in `Aligned` aligns
+// its storage.
 #[derive(Copy, Clone, Debug)]
+#[repr(C)]
 pub struct BareMetalDeque<T: Default, const MAX_STORED: usize> {
     array: [T; MAX_STORED],
     start: usize,
     size: usize,
+    #[cfg(feature = "stats")]
+    stats: QueueStats,
+}
+
+impl<T: Default, const MAX_STORED: usize> BareMetalDeque<T, MAX_STORED> {
+    /// Checks the bookkeeping fields for consistency. A deque only fails this if its memory
+    /// has been corrupted, e.g. by stray writes from unsafe code or uninitialized RAM.
+    pub fn check_invariants(&self) -> Result<(), InvariantError> {
+        Self::check_raw_invariants(self.start, self.size)
+    }
+
+    /// `check_invariants()` for a `start` and `size` read from memory that may not hold
+    /// a valid deque.
+    pub(crate) fn check_raw_invariants(start: usize, size: usize) -> Result<(), InvariantError> {
+        if start >= MAX_STORED.max(1) {
+            Err(InvariantError::StartOutOfRange)
+        } else if size > MAX_STORED {
+            Err(InvariantError::SizeOutOfRange)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// With the `check-invariants` feature, asserts `check_invariants()` in debug builds.
+    fn debug_check_invariants(&self) {
+        #[cfg(feature = "check-invariants")]
+        debug_assert_eq!(self.check_invariants(), Ok(()));
+    }
+
+    /// With the `stats` feature, counts `n` elements just added.
+    #[cfg_attr(not(feature = "stats"), allow(unused_variables))]
+    fn record_pushes(&mut self, n: usize) {
+        #[cfg(feature = "stats")]
+        self.stats.record_pushes(n, self.size);
+    }
+
+    #[cfg_attr(not(feature = "stats"), allow(unused_variables))]
+    fn record_pops(&mut self, n: usize) {
+        #[cfg(feature = "stats")]
+        self.stats.record_pops(n);
+    }
+
+    #[cfg_attr(not(feature = "stats"), allow(unused_variables))]
+    fn record_rejects(&mut self, n: usize) {
+        #[cfg(feature = "stats")]
+        self.stats.record_rejects(n);
+    }
+
+    #[cfg_attr(not(feature = "stats"), allow(unused_variables))]
+    fn record_evictions(&mut self, n: usize) {
+        #[cfg(feature = "stats")]
+        self.stats.record_evictions(n);
+    }
+
+    /// The storage slot `offset` places after the front, wrapping around the ring. Unlike
+    /// indexing, this does not check `offset` against `len()`, so beyond the contents it
+    /// yields stale or default values. Panics if the capacity is zero.
+    pub fn get_wrapped(&self, offset: usize) -> &T {
+        &self.array[self.physical(offset)]
+    }
+
+    /// The mutable counterpart of `get_wrapped()`.
+    pub fn get_wrapped_mut(&mut self, offset: usize) -> &mut T {
+        &mut self.array[self.physical(offset)]
+    }
+
+    fn check_index(&self, index: usize) {
+        if index >= self.size {
+            panic!("Index out of bounds");
+        }
+    }
+
+    /// Maps an offset from the front to an array index. Offsets within two laps of the
+    /// ring wrap by compare-and-subtract, since `%` is a software divide on Cortex-M0.
+    fn physical(&self, offset: usize) -> usize {
+        let index = self.start + offset;
+        if index < MAX_STORED {
+            index
+        } else if index - MAX_STORED < MAX_STORED {
+            index - MAX_STORED
+        } else {
+            index.checked_rem(MAX_STORED).unwrap_or(0)
+        }
+    }
 }
 
 impl<T: Default, const MAX_STORED: usize> Index<usize> for BareMetalDeque<T, MAX_STORED> {
     type Output = T;
 
+    /// Panics if `index >= len()`.
     fn index(&self, index: usize) -> &Self::Output {
-        &self.array[(self.start + index) % self.array.len()]
+        self.check_index(index);
+        self.get_wrapped(index)
     }
 }
 
 impl<T: Default, const MAX_STORED: usize> IndexMut<usize> for BareMetalDeque<T, MAX_STORED> {
+    /// Panics if `index >= len()`.
     fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        &mut self.array[(self.start + index) % self.array.len()]
+        self.check_index(index);
+        self.get_wrapped_mut(index)
     }
 }
 
 impl<T: Copy + Clone + Default, const MAX_STORED: usize> Default for BareMetalDeque<T, MAX_STORED> {
     fn default() -> Self {
-        Self { array: [T::default(); MAX_STORED], start: 0, size: 0 }
+        Self::new_with(T::default())
     }
 }
 
@@ -104,6 +367,117 @@ impl <T: Copy + Clone + Default, const MAX_STORED: usize> BareMetalDeque<T, MAX_
         Self::default()
     }
 
+    /// Collects `values`, failing without consuming them if there are more than fit.
+    #[cfg_attr(not(any(feature = "alloc", feature = "heapless")), allow(dead_code))]
+    pub(crate) fn try_from_exact<I: ExactSizeIterator<Item = T>>(values: I) -> Result<Self, CapacityError> {
+        if values.len() > MAX_STORED {
+            return Err(CapacityError);
+        }
+        let mut result = Self::new();
+        for value in values {
+            result.push_back(value);
+        }
+        Ok(result)
+    }
+
+    /// Creates a full deque whose element at index `i` is `init(i)`.
+    pub fn from_init_fn<F: FnMut(usize) -> T>(init: F) -> Self {
+        let mut result = Self { array: core::array::from_fn(init), ..Self::new() };
+        result.size = MAX_STORED;
+        result.record_pushes(MAX_STORED);
+        result
+    }
+
+    /// Builds a deque from a buffer the caller filled in place, such as by a decoder or a
+    /// DMA transfer into `MaybeUninit` storage. The first `len` elements become the
+    /// contents, and the remaining slots are set to `T::default()`. Panics if `len`
+    /// exceeds the capacity.
+    ///
+    /// # Safety
+    ///
+    /// The first `len` elements of `buffer` must be initialized.
+    pub unsafe fn assume_init_prefix(mut buffer: [MaybeUninit<T>; MAX_STORED], len: usize) -> Self {
+        if len > MAX_STORED {
+            panic!("Deque is full");
+        }
+        for slot in &mut buffer[len..] {
+            slot.write(T::default());
+        }
+        // SAFETY: the caller initialized the first `len` elements, and the rest were
+        // initialized above.
+        let array = buffer.map(|slot| unsafe { slot.assume_init() });
+        let mut result = Self { array, ..Self::new() };
+        result.size = len;
+        result.record_pushes(len);
+        result
+    }
+
+    /// Creates a deque of `len` elements whose element at index `i` is `f(i)`, such as a
+    /// waveform table or schedule. Panics if `len` exceeds the capacity.
+    pub fn from_fn<F: FnMut(usize) -> T>(len: usize, mut f: F) -> Self {
+        match Self::try_from_fn(len, |i| Ok::<T, core::convert::Infallible>(f(i))) {
+            Ok(result) => result,
+            Err(TryFromFnError::Capacity) => panic!("Deque is full"),
+            Err(TryFromFnError::Init(never)) => match never {},
+        }
+    }
+
+    /// Like `from_fn()`, but stops at and returns the first error from `f`, and fails
+    /// without calling `f` if `len` exceeds the capacity.
+    pub fn try_from_fn<E, F: FnMut(usize) -> Result<T, E>>(len: usize, mut f: F) -> Result<Self, TryFromFnError<E>> {
+        if len > MAX_STORED {
+            return Err(TryFromFnError::Capacity);
+        }
+        let mut result = Self::new();
+        for i in 0..len {
+            result.push_back(f(i).map_err(TryFromFnError::Init)?);
+        }
+        Ok(result)
+    }
+
+    /// Collects `values` into a new deque, failing if there are more than fit.
+    pub fn try_init_with<I: IntoIterator<Item = T>>(values: I) -> Result<Self, CapacityError> {
+        let mut result = Self::new();
+        for value in values {
+            result.try_push_back(value)?;
+        }
+        Ok(result)
+    }
+
+    /// Creates an empty deque with its unused storage set to `fill`. Unlike `new()`, this
+    /// can be called in a `const` context, such as a `static` initializer.
+    pub const fn new_with(fill: T) -> Self {
+        Self {
+            array: [fill; MAX_STORED],
+            start: 0,
+            size: 0,
+            #[cfg(feature = "stats")]
+            stats: QueueStats::new(),
+        }
+    }
+
+    /// Rebuilds a deque from storage filled elsewhere, such as by DMA, a bootloader, or
+    /// another core, whose `size` elements start at array index `start` and may wrap.
+    /// Fails if `start` and `size` do not describe valid contents.
+    pub fn from_raw_parts(array: [T; MAX_STORED], start: usize, size: usize) -> Result<Self, InvariantError> {
+        let mut result = Self {
+            array,
+            start,
+            size,
+            #[cfg(feature = "stats")]
+            stats: QueueStats::new(),
+        };
+        result.check_invariants()?;
+        result.record_pushes(size);
+        Ok(result)
+    }
+
+    /// Splits the deque into its storage, the array index of its front, and its length,
+    /// the inverse of `from_raw_parts()`.
+    pub fn into_raw_parts(self) -> ([T; MAX_STORED], usize, usize) {
+        (self.array, self.start, self.size)
+    }
+
     pub fn len(&self) -> usize {
         self.size
     }
@@ -117,60 +491,522 @@ impl <T: Copy + Clone + Default, const MAX_STORED: usize> BareMetalDeque<T, MAX_
     }
 
     pub fn iter(&self) -> impl Iterator<Item=&T> {
-        (0..self.len()).map(|i| &self[i])
+        let (front, back) = self.as_slices();
+        front.iter().chain(back)
+    }
+
+    /// Repeats the contents front to back endlessly, as for waveform tables or LED patterns.
+    /// Yields nothing if the deque is empty.
+    pub fn iter_cycle(&self) -> impl Iterator<Item=&T> {
+        let (front, back) = self.as_slices();
+        front.iter().chain(back).cycle()
     }
 
     pub fn push_front(&mut self, value: T) {
-        if self.size == self.array.len() {
+        if self.try_push_front(value).is_err() {
             panic!("Deque is full");
         }
-        self.start = (if self.start == 0 {self.array.len()} else {self.start}) - 1;
-        self.array[self.start] = value;
-        self.size += 1;
     }
 
     pub fn push_back(&mut self, value: T) {
-        if self.size == self.array.len() {
+        if self.try_push_back(value).is_err() {
             panic!("Deque is full");
         }
-        let index = (self.start + self.size) % self.array.len();
+    }
+
+    /// Like `push_front()`, but fails instead of panicking when the deque is full.
+    #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+    pub fn try_push_front(&mut self, value: T) -> Result<(), CapacityError> {
+        if self.size >= MAX_STORED {
+            self.record_rejects(1);
+            return Err(CapacityError);
+        }
+        self.start = self.physical(MAX_STORED - 1);
+        let index = self.physical(0);
         self.array[index] = value;
         self.size += 1;
+        self.record_pushes(1);
+        self.debug_check_invariants();
+        Ok(())
+    }
+
+    /// Like `push_back()`, but fails instead of panicking when the deque is full.
+    #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+    pub fn try_push_back(&mut self, value: T) -> Result<(), CapacityError> {
+        if self.size >= MAX_STORED {
+            self.record_rejects(1);
+            return Err(CapacityError);
+        }
+        let index = self.physical(self.size);
+        self.array[index] = value;
+        self.size += 1;
+        self.record_pushes(1);
+        self.debug_check_invariants();
+        Ok(())
+    }
+
+    /// Pushes `value` onto the back unless it equals the current back, returning whether it
+    /// was pushed. Panics if a push is needed and the deque is full.
+    pub fn push_back_if_changed(&mut self, value: T) -> bool
+    where
+        T: PartialEq,
+    {
+        if self.back() == Some(value) {
+            return false;
+        }
+        self.push_back(value);
+        true
+    }
+
+    /// Offers `value` to `merge` along with the current back. If `merge` folds it into the
+    /// back and returns `true`, nothing is pushed; otherwise, or if the deque is empty,
+    /// `value` is pushed onto the back. Returns whether it was merged. Panics if a push is
+    /// needed and the deque is full.
+    pub fn push_back_or_merge<F: FnOnce(&mut T, &T) -> bool>(&mut self, value: T, merge: F) -> bool {
+        if let Some(back) = self.size.checked_sub(1).and_then(|i| self.get_mut(i))
+            && merge(back, &value)
+        {
+            return true;
+        }
+        self.push_back(value);
+        false
     }
 
+    /// Pushes `value` onto the back, first making room if the deque is full by removing
+    /// the element with the lowest `priority`, the oldest one among ties. If `value` itself
+    /// ranks below everything held, it is not pushed. Returns the element dropped, if any.
+    pub fn push_back_evicting_lowest<K: Ord, F: Fn(&T) -> K>(&mut self, value: T, priority: F) -> Option<T> {
+        if !self.is_full() {
+            self.push_back(value);
+            return None;
+        }
+        let lowest = self.iter().map(&priority).enumerate().min_by(|(_, a), (_, b)| a.cmp(b));
+        let Some((lowest, lowest_key)) = lowest else {
+            self.record_rejects(1);
+            return Some(value);
+        };
+        if priority(&value) < lowest_key {
+            self.record_rejects(1);
+            return Some(value);
+        }
+        let evicted = self.remove(lowest);
+        self.record_evictions(1);
+        self.push_back(value);
+        evicted
+    }
+
+    #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
     pub fn pop_front(&mut self) -> Option<T> {
         let result = self.front();
         if result.is_some() {
-            self.start = (self.start + 1) % self.array.len();
+            self.start = self.physical(1);
             self.size -= 1;
+            self.record_pops(1);
+            self.debug_check_invariants();
         }
         result
     }
 
+    #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
     pub fn pop_back(&mut self) -> Option<T> {
         let result = self.back();
         if result.is_some() {
             self.size -= 1;
+            self.record_pops(1);
+            self.debug_check_invariants();
         }
         result
     }
 
+    #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
     pub fn front(&self) -> Option<T> {
-        if self.size > 0 {
-            Some(self.array[self.start])
+        self.get(0).copied()
+    }
+
+    #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+    pub fn back(&self) -> Option<T> {
+        self.get(self.size.checked_sub(1)?).copied()
+    }
+
+    /// Like indexing, but returns `None` instead of panicking if `index >= len()`.
+    #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        // The capacity check lets no-panic builds see that a zero-capacity deque never
+        // indexes its empty array.
+        if MAX_STORED > 0 && index < self.size {
+            Some(&self.array[self.physical(index)])
         } else {
             None
-        }        
+        }
     }
 
-    pub fn back(&self) -> Option<T> {
-        if self.size > 0 {
-            let index = (self.start + self.size - 1) % self.array.len();
-            Some(self.array[index])
+    #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if MAX_STORED > 0 && index < self.size {
+            let index = self.physical(index);
+            Some(&mut self.array[index])
         } else {
             None
         }
-        
+    }
+
+    /// Logical index of the first element, front to back, for which `pred` is true.
+    pub fn position<F: FnMut(&T) -> bool>(&self, pred: F) -> Option<usize> {
+        self.iter().position(pred)
+    }
+
+    /// Logical index of the first element equal to `value`, as when checking whether a
+    /// message is already queued.
+    pub fn index_of(&self, value: &T) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        self.position(|x| x == value)
+    }
+
+    /// Stores `value` at logical position `index` and returns the element it replaced, or
+    /// returns `None` without storing anything if `index >= len()`.
+    pub fn replace(&mut self, index: usize, value: T) -> Option<T> {
+        self.get_mut(index).map(|slot| core::mem::replace(slot, value))
+    }
+
+    /// Inserts `value` at logical position `index`, shifting whichever side of it is shorter.
+    /// Panics if the deque is full or `index > len()`.
+    pub fn insert(&mut self, index: usize, value: T) {
+        if index > self.size {
+            panic!("Insertion index out of bounds");
+        }
+        if index < self.size / 2 {
+            self.push_front(value);
+            for i in 0..index {
+                self[i] = self[i + 1];
+            }
+        } else {
+            self.push_back(value);
+            for i in (index..self.size - 1).rev() {
+                self[i + 1] = self[i];
+            }
+        }
+        self[index] = value;
+    }
+
+    /// Removes and returns the element at logical position `index`, shifting whichever side
+    /// of it is shorter. Returns `None` if `index` is out of bounds.
+    #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        if MAX_STORED == 0 || index >= self.size {
+            return None;
+        }
+        let value = self[index];
+        if index < self.size / 2 {
+            for i in (0..index).rev() {
+                self[i + 1] = self[i];
+            }
+            self.pop_front();
+        } else {
+            for i in index..self.size - 1 {
+                self[i] = self[i + 1];
+            }
+            self.pop_back();
+        }
+        Some(value)
+    }
+
+    /// Returns the logical contents as two contiguous slices, front segment first.
+    /// The second slice is empty unless the contents wrap around the end of the ring.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let first_len = self.size.min(MAX_STORED - self.start);
+        (&self.array[self.start..self.start + first_len], &self.array[..self.size - first_len])
+    }
+
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let first_len = self.size.min(MAX_STORED - self.start);
+        let (wrapped, rest) = self.array.split_at_mut(self.start);
+        (&mut rest[..first_len], &mut wrapped[..self.size - first_len])
+    }
+
+    /// Rotates the storage so the contents are contiguous, starting at the front of the
+    /// backing array, and returns them as one slice.
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        self.array.rotate_left(self.start);
+        self.start = 0;
+        &mut self.array[..self.size]
+    }
+
+    /// The first `K` elements as an array, for matching fixed-size headers. Returns `None`
+    /// if there are fewer than `K` elements or they wrap around the end of the ring; calling
+    /// `make_contiguous()` first rules out the latter.
+    pub fn first_chunk<const K: usize>(&self) -> Option<&[T; K]> {
+        self.as_slices().0.first_chunk()
+    }
+
+    /// The last `K` elements as an array. Returns `None` if there are fewer than `K`
+    /// elements or they wrap around the end of the ring.
+    pub fn last_chunk<const K: usize>(&self) -> Option<&[T; K]> {
+        match self.as_slices() {
+            (front, []) => front.last_chunk(),
+            (_, back) => back.last_chunk(),
+        }
+    }
+
+    /// Pointer to the start of the backing array, which holds `MAX_STORED` elements.
+    pub fn as_ptr(&self) -> *const T {
+        self.array.as_ptr()
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.array.as_mut_ptr()
+    }
+
+    /// Start and length of the occupied regions of the backing array, front segment first,
+    /// as in `as_slices()`. The second region has length zero unless the contents wrap.
+    pub fn occupied_regions(&self) -> [(*const T, usize); 2] {
+        let (front, back) = self.as_slices();
+        [(front.as_ptr(), front.len()), (back.as_ptr(), back.len())]
+    }
+
+    /// Start and length of the free regions of the backing array, in the order `push_back()`
+    /// fills them. The second region has length zero unless the free space wraps.
+    pub fn free_regions(&mut self) -> [(*mut T, usize); 2] {
+        let free = MAX_STORED - self.size;
+        let tail = if free == 0 { 0 } else { self.physical(self.size) };
+        let first = free.min(MAX_STORED - tail);
+        let base = self.array.as_mut_ptr();
+        [(base.wrapping_add(tail), first), (base, free - first)]
+    }
+
+    /// Appends as much of `src` as fits, returning the number of elements appended.
+    /// Copies at most two contiguous segments rather than one element at a time.
+    pub fn extend_from_slice(&mut self, src: &[T]) -> usize {
+        let n = src.len().min(MAX_STORED - self.size);
+        self.record_rejects(src.len() - n);
+        if n == 0 {
+            return 0;
+        }
+        let tail = self.physical(self.size);
+        let first = n.min(MAX_STORED - tail);
+        self.array[tail..tail + first].copy_from_slice(&src[..first]);
+        self.array[..n - first].copy_from_slice(&src[first..n]);
+        self.size += n;
+        self.record_pushes(n);
+        self.debug_check_invariants();
+        n
+    }
+
+    /// Exchanges the logical contents with `other`, element for element, using at most two
+    /// contiguous swaps. Panics if `other.len() != len()`.
+    pub fn swap_with_slice(&mut self, other: &mut [T]) {
+        if other.len() != self.size {
+            panic!("Slice length does not match deque length");
+        }
+        let (front, back) = self.as_mut_slices();
+        let (other_front, other_back) = other.split_at_mut(front.len());
+        front.swap_with_slice(other_front);
+        back.swap_with_slice(other_back);
+    }
+
+    /// Prepends all of `src`, so that afterwards the front `src.len()` elements equal `src`,
+    /// as when un-reading input. Fails, leaving the deque unchanged, if `src` does not fit.
+    pub fn push_front_slice(&mut self, src: &[T]) -> Result<(), CapacityError> {
+        if src.len() > MAX_STORED - self.size {
+            self.record_rejects(src.len());
+            return Err(CapacityError);
+        }
+        if src.is_empty() {
+            return Ok(());
+        }
+        self.start = self.physical(MAX_STORED - src.len());
+        self.size += src.len();
+        self.record_pushes(src.len());
+        self.debug_check_invariants();
+        self.view_mut(..src.len()).copy_from_slice(src);
+        Ok(())
+    }
+
+    /// Prepends the items of `values`, keeping their order, so the first item becomes the
+    /// new front. If they do not all fit, fails and leaves the deque unchanged.
+    pub fn extend_front<I: IntoIterator<Item = T>>(&mut self, values: I) -> Result<(), CapacityError> {
+        let mut added = 0;
+        for value in values {
+            if self.try_push_front(value).is_err() {
+                self.discard(added);
+                return Err(CapacityError);
+            }
+            added += 1;
+        }
+        for i in 0..added / 2 {
+            let tmp = self[i];
+            self[i] = self[added - 1 - i];
+            self[added - 1 - i] = tmp;
+        }
+        Ok(())
+    }
+
+    /// Inserts all of `src` starting at logical position `index`, shifting whichever side of
+    /// it is shorter. Fails, leaving the deque unchanged, if `src` does not fit. Panics if
+    /// `index > len()`.
+    pub fn insert_slice(&mut self, index: usize, src: &[T]) -> Result<(), CapacityError> {
+        if index > self.size {
+            panic!("Insertion index out of bounds");
+        }
+        let n = src.len();
+        if n > MAX_STORED - self.size {
+            self.record_rejects(n);
+            return Err(CapacityError);
+        }
+        if n == 0 {
+            return Ok(());
+        }
+        if index < self.size - index {
+            self.start = self.physical(MAX_STORED - n);
+            self.move_within(n, 0, index);
+        } else {
+            self.move_within(index, index + n, self.size - index);
+        }
+        self.size += n;
+        self.record_pushes(n);
+        self.debug_check_invariants();
+        self.view_mut(index..index + n).copy_from_slice(src);
+        Ok(())
+    }
+
+    /// Removes the elements in `range`, closing the gap by moving whichever side of it is
+    /// shorter. Panics if the range is out of bounds.
+    pub fn remove_range<R: RangeBounds<usize>>(&mut self, range: R) {
+        let (a, b) = self.range_bounds(range);
+        let removed = b - a;
+        if removed == 0 {
+            return;
+        }
+        if a < self.size - b {
+            self.move_within(0, removed, a);
+            self.start = self.physical(removed);
+        } else {
+            self.move_within(b, a, self.size - b);
+        }
+        self.size -= removed;
+        self.record_pops(removed);
+        self.debug_check_invariants();
+    }
+
+    /// Copies elements from the front into `dst` without removing them, returning the
+    /// number of elements copied.
+    pub fn copy_to_slice(&self, dst: &mut [T]) -> usize {
+        let n = dst.len().min(self.size);
+        let (front, back) = self.as_slices();
+        let first = n.min(front.len());
+        dst[..first].copy_from_slice(&front[..first]);
+        dst[first..n].copy_from_slice(&back[..n - first]);
+        n
+    }
+
+    /// Removes elements from the front into `dst`, returning the number removed.
+    pub fn pop_front_into(&mut self, dst: &mut [T]) -> usize {
+        let n = self.copy_to_slice(dst);
+        self.discard(n);
+        n
+    }
+
+    /// Removes exactly `K` elements from the front, returning them in order, or returns
+    /// `None` and leaves the deque unchanged if there are fewer than `K`.
+    pub fn pop_front_array<const K: usize>(&mut self) -> Option<[T; K]> {
+        if self.size < K {
+            return None;
+        }
+        let mut result = [T::default(); K];
+        self.pop_front_into(&mut result);
+        Some(result)
+    }
+
+    /// Removes exactly `K` elements from the back, returning them in order, or returns
+    /// `None` and leaves the deque unchanged if there are fewer than `K`.
+    pub fn pop_back_array<const K: usize>(&mut self) -> Option<[T; K]> {
+        if self.size < K {
+            return None;
+        }
+        let mut result = [T::default(); K];
+        self.view(self.size - K..).copy_to_slice(&mut result);
+        self.size -= K;
+        self.record_pops(K);
+        self.debug_check_invariants();
+        Some(result)
+    }
+
+    /// Pops at most `n` elements from the front, so a consumer can bound the work it does
+    /// per pass. Elements are popped lazily: any the iterator does not reach stay queued.
+    pub fn pop_batch(&mut self, n: usize) -> impl Iterator<Item=T> + '_ {
+        core::iter::from_fn(move || self.pop_front()).take(n)
+    }
+
+    /// Copies `count` elements from logical offset `from` to logical offset `to`, both
+    /// relative to `start` and less than `2 * MAX_STORED`, as a memmove over at most a few
+    /// contiguous pieces. The ranges may overlap and wrap.
+    fn move_within(&mut self, from: usize, to: usize, count: usize) {
+        if from > to {
+            let mut done = 0;
+            while done < count {
+                let (src, dst) = (self.physical(from + done), self.physical(to + done));
+                let n = (count - done).min(MAX_STORED - src).min(MAX_STORED - dst);
+                self.array.copy_within(src..src + n, dst);
+                done += n;
+            }
+        } else if from < to {
+            let mut remaining = count;
+            while remaining > 0 {
+                let src_end = self.physical(from + remaining - 1) + 1;
+                let dst_end = self.physical(to + remaining - 1) + 1;
+                let n = remaining.min(src_end).min(dst_end);
+                self.array.copy_within(src_end - n..src_end, dst_end - n);
+                remaining -= n;
+            }
+        }
+    }
+
+    /// Drops `n <= len()` elements from the front.
+    pub(crate) fn discard(&mut self, n: usize) {
+        if n > 0 {
+            self.start = self.physical(n);
+            self.size -= n;
+            self.record_pops(n);
+            self.debug_check_invariants();
+        }
+    }
+}
+
+/// Compares logical contents, segment against segment, regardless of where each deque's
+/// contents start in its ring or what capacity it has.
+impl<T, const M: usize, const N: usize> PartialEq<BareMetalDeque<T, N>> for BareMetalDeque<T, M>
+where
+    T: Copy + Clone + Default + PartialEq,
+{
+    fn eq(&self, other: &BareMetalDeque<T, N>) -> bool {
+        view::segments_eq(self.as_slices(), other.as_slices())
+    }
+}
+
+impl<T: Copy + Clone + Default + Eq, const MAX_STORED: usize> Eq for BareMetalDeque<T, MAX_STORED> {}
+
+impl<T: Copy + Clone + Default + Ord, const MAX_STORED: usize> BareMetalDeque<T, MAX_STORED> {
+    /// Smallest element, or `None` if the deque is empty.
+    pub fn min(&self) -> Option<T> {
+        let (front, back) = self.as_slices();
+        front.iter().min().into_iter().chain(back.iter().min()).min().copied()
+    }
+
+    /// Largest element, or `None` if the deque is empty.
+    pub fn max(&self) -> Option<T> {
+        let (front, back) = self.as_slices();
+        front.iter().max().into_iter().chain(back.iter().max()).max().copied()
+    }
+
+    /// Smallest and largest elements found in a single pass, as for range-checking a
+    /// window of samples, or `None` if the deque is empty.
+    pub fn minmax(&self) -> Option<(T, T)> {
+        let (front, back) = self.as_slices();
+        let first = *front.first()?;
+        let extend = |(lo, hi): (T, T), x: &T| (lo.min(*x), hi.max(*x));
+        let bounds = front[1..].iter().fold((first, first), extend);
+        Some(back.iter().fold(bounds, extend))
     }
 }
 
@@ -298,4 +1134,334 @@ mod tests {
             assert_eq!(x, stack.pop_back().unwrap());
         }
     }
+
+    #[test]
+    fn as_slices_test() {
+        let mut q = BareMetalDeque::<usize, 4>::new();
+        assert_eq!(q.as_slices(), (&[][..], &[][..]));
+        for x in 1..=4 {
+            q.push_back(x);
+        }
+        assert_eq!(q.as_slices(), (&[1, 2, 3, 4][..], &[][..]));
+        q.pop_front();
+        q.pop_front();
+        q.push_back(5);
+        assert_eq!(q.as_slices(), (&[3, 4][..], &[5][..]));
+
+        let (front, back) = q.as_mut_slices();
+        front[0] = 6;
+        back[0] = 7;
+        assert_eq!(q.iter().copied().collect::<Vec<_>>(), vec![6, 4, 7]);
+    }
+
+    #[test]
+    fn insert_remove_test() {
+        let mut q = BareMetalDeque::<usize, 6>::new();
+        for x in [0, 1, 2] {
+            q.push_back(x);
+        }
+        q.pop_front();
+        q.pop_front();
+        q.insert(0, 1);
+        q.insert(2, 4);
+        q.insert(2, 3);
+        q.insert(0, 0);
+        q.insert(5, 5);
+        assert_eq!(q.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2, 3, 4, 5]);
+
+        assert_eq!(q.remove(6), None);
+        assert_eq!(q.remove(1), Some(1));
+        assert_eq!(q.remove(3), Some(4));
+        assert_eq!(q.remove(0), Some(0));
+        assert_eq!(q.iter().copied().collect::<Vec<_>>(), vec![2, 3, 5]);
+    }
+
+    #[test]
+    fn bulk_copy_test() {
+        let mut q = BareMetalDeque::<i32, 5>::new();
+        assert_eq!(q.extend_from_slice(&[1, 2, 3, 4]), 4);
+        let mut dst = [0; 3];
+        assert_eq!(q.pop_front_into(&mut dst), 3);
+        assert_eq!(dst, [1, 2, 3]);
+        assert_eq!(q.extend_from_slice(&[5, 6, 7, 8, 9]), 4);
+        assert_eq!(q.as_slices(), (&[4, 5][..], &[6, 7, 8][..]));
+
+        let mut unwrapped = BareMetalDeque::<i32, 8>::new();
+        unwrapped.extend_from_slice(&[4, 5, 6, 7, 8]);
+        assert_eq!(q, unwrapped);
+        assert_eq!(unwrapped, q);
+        unwrapped.pop_back();
+        assert_ne!(q, unwrapped);
+        unwrapped.push_back(0);
+        assert_ne!(unwrapped, q);
+
+        let mut dst = [0; 8];
+        assert_eq!(q.copy_to_slice(&mut dst), 5);
+        assert_eq!(dst[..5], [4, 5, 6, 7, 8]);
+        assert_eq!(q.len(), 5);
+    }
+
+    #[test]
+    fn raw_regions_test() {
+        let mut q = BareMetalDeque::<u16, 5>::new();
+        let base = q.as_mut_ptr();
+        assert_eq!(q.free_regions(), [(base, 5), (base, 0)]);
+
+        q.extend_from_slice(&[1, 2, 3, 4]);
+        q.pop_front_into(&mut [0; 2]);
+        let base = q.as_ptr();
+        assert_eq!(q.occupied_regions(), [(base.wrapping_add(2), 2), (base, 0)]);
+        assert_eq!(q.free_regions(), [(base.wrapping_add(4) as *mut u16, 1), (base as *mut u16, 2)]);
+
+        q.extend_from_slice(&[5, 6, 7]);
+        assert_eq!(q.occupied_regions(), [(base.wrapping_add(2), 3), (base, 2)]);
+        assert_eq!(q.free_regions()[0].1 + q.free_regions()[1].1, 0);
+    }
+
+    #[test]
+    fn fallible_access_test() {
+        let mut q = BareMetalDeque::<u8, 2>::new();
+        assert_eq!(q.get(0), None);
+        assert_eq!(q.try_push_back(1), Ok(()));
+        assert_eq!(q.try_push_front(0), Ok(()));
+        assert_eq!(q.try_push_back(2), Err(CapacityError));
+        assert_eq!(q.try_push_front(2), Err(CapacityError));
+        *q.get_mut(1).unwrap() += 10;
+        assert_eq!((q.get(0), q.get(1), q.get(2)), (Some(&0), Some(&11), None));
+        assert!(q.get_mut(2).is_none());
+        assert_eq!(BareMetalDeque::<u8, 0>::new().try_push_front(0), Err(CapacityError));
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds")]
+    fn stale_index_test() {
+        let mut q = BareMetalDeque::<u8, 3>::new();
+        q.extend_from_slice(&[1, 2, 3]);
+        q.pop_back();
+        *q.get_wrapped_mut(3) += 10;
+        assert_eq!((q[0], *q.get_wrapped(2), *q.get_wrapped(3)), (11, 3, 11));
+        let _ = q[2];
+    }
+
+    #[test]
+    fn position_test() {
+        let mut q = BareMetalDeque::<(u8, u8), 4>::new();
+        q.extend_from_slice(&[(0, 0); 3]);
+        q.discard(3);
+        q.extend_from_slice(&[(1, 10), (2, 20), (1, 30)]);
+        assert_eq!(q.index_of(&(1, 30)), Some(2));
+        assert_eq!(q.index_of(&(0, 0)), None);
+        assert_eq!(q.position(|(id, _)| *id == 1), Some(0));
+        assert_eq!(q.position(|(_, v)| *v > 10), Some(1));
+    }
+
+    #[test]
+    fn minmax_test() {
+        let mut q = BareMetalDeque::<i16, 5>::new();
+        assert_eq!((q.min(), q.max(), q.minmax()), (None, None, None));
+        q.extend_from_slice(&[9, 9, 9]);
+        q.discard(3);
+        q.extend_from_slice(&[4, -7, 12, 0, 3]);
+        assert_eq!(q.as_slices(), (&[4, -7][..], &[12, 0, 3][..]));
+        assert_eq!((q.min(), q.max()), (Some(-7), Some(12)));
+        assert_eq!(q.minmax(), Some((-7, 12)));
+    }
+
+    #[test]
+    fn invariants_test() {
+        let mut q = BareMetalDeque::<u8, 3>::new();
+        q.extend_from_slice(&[1, 2, 3]);
+        assert_eq!(q.check_invariants(), Ok(()));
+        q.start = 3;
+        assert_eq!(q.check_invariants(), Err(InvariantError::StartOutOfRange));
+        q.start = 2;
+        q.size = 4;
+        assert_eq!(q.check_invariants(), Err(InvariantError::SizeOutOfRange));
+        assert_eq!(BareMetalDeque::<u8, 0>::new().check_invariants(), Ok(()));
+
+        let (array, start, size) = q.into_raw_parts();
+        assert_eq!((start, size), (2, 4));
+        assert_eq!(BareMetalDeque::from_raw_parts(array, 2, 4).unwrap_err(), InvariantError::SizeOutOfRange);
+        let q = BareMetalDeque::from_raw_parts([7, 8, 9], 2, 2).unwrap();
+        assert_eq!(q.view(..), [9, 7]);
+    }
+
+    #[test]
+    fn init_test() {
+        let squares = BareMetalDeque::<usize, 4>::from_init_fn(|i| i * i);
+        assert!(squares.is_full());
+        assert_eq!(squares.iter().copied().collect::<Vec<_>>(), vec![0, 1, 4, 9]);
+
+        let q = BareMetalDeque::<char, 3>::try_init_with("ab".chars()).unwrap();
+        assert_eq!((q.len(), q.back()), (2, Some('b')));
+        assert_eq!(BareMetalDeque::<char, 3>::try_init_with("abcd".chars()).unwrap_err(), CapacityError);
+
+        let mut buffer = [MaybeUninit::<u16>::uninit(); 4];
+        buffer[0].write(7);
+        buffer[1].write(8);
+        let q = unsafe { BareMetalDeque::assume_init_prefix(buffer, 2) };
+        assert_eq!(q.iter().copied().collect::<Vec<_>>(), vec![7, 8]);
+
+        let ramp = BareMetalDeque::<i16, 8>::from_fn(3, |i| i as i16 * -100);
+        assert_eq!(ramp.view(..), [0, -100, -200]);
+        let table = BareMetalDeque::<u8, 8>::try_from_fn(4, |i| u8::try_from(i * 100));
+        assert!(matches!(table, Err(TryFromFnError::Init(_))));
+        assert_eq!(BareMetalDeque::<u8, 8>::try_from_fn(3, |i| u8::try_from(i * 100)).unwrap().back(), Some(200));
+        let oversized = BareMetalDeque::<u8, 2>::try_from_fn(3, |_| -> Result<u8, ()> { unreachable!() });
+        assert_eq!(oversized.unwrap_err(), TryFromFnError::Capacity);
+    }
+
+    #[test]
+    fn chunk_test() {
+        let mut q = BareMetalDeque::<u8, 6>::new();
+        q.extend_from_slice(b"....ab");
+        q.discard(4);
+        q.extend_from_slice(b"cde");
+        assert_eq!(q.first_chunk(), Some(b"ab"));
+        assert_eq!(q.first_chunk::<3>(), None);
+        assert_eq!(q.last_chunk(), Some(b"cde"));
+        assert_eq!(q.last_chunk::<4>(), None);
+
+        assert_eq!(q.make_contiguous(), b"abcde");
+        assert_eq!(q.first_chunk(), Some(b"abcde"));
+        assert_eq!(q.last_chunk(), Some(b"bcde"));
+        assert_eq!(q.first_chunk::<6>(), None);
+        assert_eq!(q.first_chunk::<0>(), Some(&[]));
+    }
+
+    #[test]
+    fn pop_array_test() {
+        let mut q = BareMetalDeque::<u16, 4>::new();
+        q.extend_from_slice(&[9, 9, 1, 2]);
+        q.discard(2);
+        q.extend_from_slice(&[3, 4]);
+        assert_eq!(q.pop_front_array::<5>(), None);
+        assert_eq!(q.pop_back_array(), Some([2, 3, 4]));
+        assert_eq!(q.pop_back_array::<2>(), None);
+        assert_eq!(q.pop_front_array(), Some([1]));
+        assert_eq!(q.pop_front_array(), Some([]));
+        assert!(q.is_empty());
+
+        q.extend_from_slice(&[5, 6, 7]);
+        assert_eq!(q.pop_batch(2).collect::<Vec<_>>(), vec![5, 6]);
+        assert_eq!(q.pop_batch(2).next(), Some(7));
+        assert_eq!(q.pop_batch(2).count(), 0);
+    }
+
+    #[test]
+    fn prepend_test() {
+        let mut q = BareMetalDeque::<u8, 6>::new();
+        q.extend_from_slice(b"cd");
+        assert_eq!(q.push_front_slice(b"ab"), Ok(()));
+        assert_eq!(q.push_front_slice(b"xyz"), Err(CapacityError));
+        assert_eq!(q.extend_front(*b"xyz"), Err(CapacityError));
+        assert_eq!(q.view(..), *b"abcd");
+        assert_eq!(q.extend_front(*b"01"), Ok(()));
+        assert_eq!(q.view(..), *b"01abcd");
+        assert_eq!(q.push_front_slice(b""), Ok(()));
+    }
+
+    #[test]
+    fn insert_slice_test() {
+        for start in 0..7 {
+            for index in 0..=4 {
+                let mut q = BareMetalDeque::<u8, 7>::new();
+                q.start = start;
+                q.extend_from_slice(b"abcd");
+                assert_eq!(q.insert_slice(index, b"XYZ"), Ok(()));
+                let mut expected = b"abcd".to_vec();
+                expected.splice(index..index, *b"XYZ");
+                assert_eq!(q.view(..), expected[..], "start {start}, index {index}");
+                assert_eq!(q.insert_slice(index, b"!"), Err(CapacityError));
+            }
+        }
+    }
+
+    #[test]
+    fn remove_range_test() {
+        for start in 0..6 {
+            for (a, b) in [(0, 0), (0, 2), (1, 3), (2, 5), (4, 6), (0, 6)] {
+                let mut q = BareMetalDeque::<u8, 6>::new();
+                q.start = start;
+                q.extend_from_slice(b"abcdef");
+                q.remove_range(a..b);
+                let mut expected = b"abcdef".to_vec();
+                expected.drain(a..b);
+                assert_eq!(q.view(..), expected[..], "start {start}, range {a}..{b}");
+            }
+        }
+    }
+
+    #[test]
+    fn replace_test() {
+        let mut q = BareMetalDeque::<char, 3>::new();
+        q.push_back('a');
+        q.push_front('b');
+        assert_eq!(q.replace(1, 'c'), Some('a'));
+        assert_eq!(q.replace(2, 'd'), None);
+        assert_eq!(q.view(..), ['b', 'c']);
+    }
+
+    #[test]
+    fn swap_with_slice_test() {
+        let mut q = BareMetalDeque::<i32, 4>::new();
+        q.extend_from_slice(&[0, 0, 1, 2]);
+        q.discard(2);
+        q.extend_from_slice(&[3]);
+        let mut other = [7, 8, 9];
+        q.swap_with_slice(&mut other);
+        assert_eq!(other, [1, 2, 3]);
+        assert_eq!(q.view(..), [7, 8, 9]);
+    }
+
+    #[test]
+    fn iter_cycle_test() {
+        let mut q = BareMetalDeque::<u8, 3>::new();
+        assert_eq!(q.iter_cycle().next(), None);
+        q.extend_from_slice(&[0, 1, 2]);
+        q.discard(1);
+        q.push_back(3);
+        assert_eq!(q.iter_cycle().take(7).copied().collect::<Vec<_>>(), vec![1, 2, 3, 1, 2, 3, 1]);
+    }
+
+    #[test]
+    fn push_if_changed_test() {
+        let mut states = BareMetalDeque::<u8, 3>::new();
+        let pushed: Vec<bool> = [1, 1, 2, 2, 2, 1].into_iter().map(|s| states.push_back_if_changed(s)).collect();
+        assert_eq!(pushed, vec![true, false, true, false, false, true]);
+        assert_eq!(states.view(..), [1, 2, 1]);
+    }
+
+    #[test]
+    fn push_or_merge_test() {
+        // (event code, repeat count)
+        let mut events = BareMetalDeque::<(u8, u8), 4>::new();
+        let bump = |back: &mut (u8, u8), new: &(u8, u8)| {
+            let same = back.0 == new.0;
+            if same {
+                back.1 += new.1;
+            }
+            same
+        };
+        assert!(!events.push_back_or_merge((7, 1), bump));
+        assert!(events.push_back_or_merge((7, 1), bump));
+        assert!(!events.push_back_or_merge((3, 1), bump));
+        assert!(events.push_back_or_merge((3, 2), bump));
+        assert_eq!(events.view(..), [(7, 2), (3, 3)]);
+    }
+
+    #[test]
+    fn priority_eviction_test() {
+        // (priority, id)
+        let mut q = BareMetalDeque::<(u8, u8), 3>::new();
+        let priority = |m: &(u8, u8)| m.0;
+        for m in [(1, 0), (5, 1), (1, 2)] {
+            assert_eq!(q.push_back_evicting_lowest(m, priority), None);
+        }
+        assert_eq!(q.push_back_evicting_lowest((9, 3), priority), Some((1, 0)));
+        assert_eq!(q.push_back_evicting_lowest((0, 4), priority), Some((0, 4)));
+        assert_eq!(q.push_back_evicting_lowest((1, 5), priority), Some((1, 2)));
+        assert_eq!(q.view(..), [(5, 1), (9, 3), (1, 5)]);
+        assert_eq!(BareMetalDeque::<u8, 0>::new().push_back_evicting_lowest(1, |x| *x), Some(1));
+    }
 }
@@ -0,0 +1,160 @@
+//! An editable single-line buffer with a cursor, for serial consoles and command shells.
+//!
+//! ```
+//! use bare_metal_deque::LineEditor;
+//!
+//! let mut editor = LineEditor::<16>::new();
+//! editor.insert_str(b"led of");
+//! editor.home();
+//! editor.insert_str(b"set ");
+//! editor.end();
+//! editor.insert(b'f');
+//!
+//! let mut line = [0; 16];
+//! let len = editor.render(&mut line);
+//! assert_eq!(&line[..len], b"set led off");
+//! assert_eq!(editor.cursor(), 11);
+//! ```
+
+use crate::ByteDeque;
+
+#[derive(Copy, Clone, Debug)]
+pub struct LineEditor<const N: usize> {
+    line: ByteDeque<N>,
+    cursor: usize,
+}
+
+impl<const N: usize> Default for LineEditor<N> {
+    fn default() -> Self {
+        Self { line: ByteDeque::new(), cursor: 0 }
+    }
+}
+
+impl<const N: usize> LineEditor<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.line.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.line.is_empty()
+    }
+
+    /// Position of the cursor, from `0` (before the first byte) to `len()` (after the last).
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Inserts `b` before the cursor and advances past it. Returns `false` if the line is full.
+    pub fn insert(&mut self, b: u8) -> bool {
+        if self.line.is_full() {
+            return false;
+        }
+        self.line.insert(self.cursor, b);
+        self.cursor += 1;
+        true
+    }
+
+    /// Inserts as much of `s` as fits, returning the number of bytes inserted.
+    pub fn insert_str(&mut self, s: &[u8]) -> usize {
+        s.iter().take_while(|b| self.insert(**b)).count()
+    }
+
+    /// Deletes the byte before the cursor. Returns `false` if the cursor is at the start.
+    pub fn backspace(&mut self) -> bool {
+        if self.cursor == 0 {
+            return false;
+        }
+        self.cursor -= 1;
+        self.line.remove(self.cursor);
+        true
+    }
+
+    /// Deletes the byte under the cursor. Returns `false` if the cursor is at the end.
+    pub fn delete(&mut self) -> bool {
+        self.line.remove(self.cursor).is_some()
+    }
+
+    pub fn left(&mut self) -> bool {
+        if self.cursor == 0 {
+            return false;
+        }
+        self.cursor -= 1;
+        true
+    }
+
+    pub fn right(&mut self) -> bool {
+        if self.cursor == self.line.len() {
+            return false;
+        }
+        self.cursor += 1;
+        true
+    }
+
+    pub fn home(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn end(&mut self) {
+        self.cursor = self.line.len();
+    }
+
+    /// Copies the current line into `dst`, returning the number of bytes copied.
+    pub fn render(&self, dst: &mut [u8]) -> usize {
+        self.line.peek(dst)
+    }
+
+    /// Copies the current line into `dst` and clears the editor, as on pressing Enter.
+    pub fn take_line(&mut self, dst: &mut [u8]) -> usize {
+        let len = self.render(dst);
+        self.clear();
+        len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rendered<const N: usize>(editor: &LineEditor<N>) -> Vec<u8> {
+        let mut line = [0; N];
+        let len = editor.render(&mut line);
+        line[..len].to_vec()
+    }
+
+    #[test]
+    fn editing_test() {
+        let mut editor = LineEditor::<6>::new();
+        assert!(!editor.backspace());
+        assert!(!editor.delete());
+        assert_eq!(editor.insert_str(b"abcdefgh"), 6);
+        assert!(!editor.insert(b'x'));
+        assert!(!editor.right());
+
+        assert!(editor.left());
+        assert!(editor.left());
+        assert!(editor.backspace());
+        assert_eq!(rendered(&editor), b"abcef");
+        assert!(editor.delete());
+        assert_eq!(rendered(&editor), b"abcf");
+        assert_eq!(editor.cursor(), 3);
+
+        editor.home();
+        assert!(!editor.left());
+        assert!(editor.delete());
+        assert!(editor.insert(b'z'));
+        assert_eq!(rendered(&editor), b"zbcf");
+
+        let mut line = [0; 6];
+        assert_eq!(editor.take_line(&mut line), 4);
+        assert!(editor.is_empty());
+        assert_eq!(editor.cursor(), 0);
+    }
+}
@@ -0,0 +1,118 @@
+//! A single-core channel over a deque, so application modules can pass messages through
+//! `Sender` and `Receiver` handles without sharing the deque type itself.
+//!
+//! The handles borrow a `RefCell` holding the deque and use no atomics, so they suit
+//! cooperative schedulers on one core. Use [`SharedDeque`](crate::SharedDeque) to share
+//! data with interrupt handlers instead.
+//!
+//! ```
+//! use core::cell::RefCell;
+//! use bare_metal_deque::{BareMetalDeque, RecvError, SendError, channel};
+//!
+//! let queue = RefCell::new(BareMetalDeque::<u16, 2>::new());
+//! let (tx, rx) = channel(&queue);
+//! let sensor_task = tx;
+//! sensor_task.try_send(10).unwrap();
+//! tx.try_send(11).unwrap();
+//! assert_eq!(tx.try_send(12), Err(SendError(12)));
+//! assert_eq!(rx.try_recv(), Ok(10));
+//! assert_eq!(rx.try_recv(), Ok(11));
+//! assert_eq!(rx.try_recv(), Err(RecvError));
+//! ```
+
+use core::cell::RefCell;
+
+use crate::BareMetalDeque;
+
+/// Returned by `try_send()` when the channel is full, holding the unsent value.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+/// Returned by `try_recv()` when the channel is empty.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RecvError;
+
+#[derive(Debug)]
+pub struct Sender<'a, T: Default, const MAX_STORED: usize> {
+    queue: &'a RefCell<BareMetalDeque<T, MAX_STORED>>,
+}
+
+#[derive(Debug)]
+pub struct Receiver<'a, T: Default, const MAX_STORED: usize> {
+    queue: &'a RefCell<BareMetalDeque<T, MAX_STORED>>,
+}
+
+// Handles are shared references, so they copy whatever `T` is.
+impl<T: Default, const MAX_STORED: usize> Clone for Sender<'_, T, MAX_STORED> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Default, const MAX_STORED: usize> Copy for Sender<'_, T, MAX_STORED> {}
+
+impl<T: Default, const MAX_STORED: usize> Clone for Receiver<'_, T, MAX_STORED> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Default, const MAX_STORED: usize> Copy for Receiver<'_, T, MAX_STORED> {}
+
+/// Splits `queue` into sending and receiving handles. Values are received in the order
+/// they were sent.
+pub fn channel<T: Copy + Default, const MAX_STORED: usize>(
+    queue: &RefCell<BareMetalDeque<T, MAX_STORED>>,
+) -> (Sender<'_, T, MAX_STORED>, Receiver<'_, T, MAX_STORED>) {
+    (Sender { queue }, Receiver { queue })
+}
+
+impl<T: Copy + Default, const MAX_STORED: usize> Sender<'_, T, MAX_STORED> {
+    /// Queues `value`, or hands it back if the channel is full.
+    pub fn try_send(&self, value: T) -> Result<(), SendError<T>> {
+        self.queue.borrow_mut().try_push_back(value).map_err(|_| SendError(value))
+    }
+
+    /// Whether a send would currently fail.
+    pub fn is_full(&self) -> bool {
+        self.queue.borrow().is_full()
+    }
+}
+
+impl<T: Copy + Default, const MAX_STORED: usize> Receiver<'_, T, MAX_STORED> {
+    /// Takes the oldest queued value, if any.
+    pub fn try_recv(&self) -> Result<T, RecvError> {
+        self.queue.borrow_mut().pop_front().ok_or(RecvError)
+    }
+
+    /// Number of values waiting.
+    pub fn len(&self) -> usize {
+        self.queue.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.borrow().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn channel_test() {
+        let queue = RefCell::new(BareMetalDeque::<(u8, char), 3>::new());
+        let (tx, rx) = channel(&queue);
+        let senders = [tx, tx];
+        for (i, sender) in senders.iter().enumerate() {
+            sender.try_send((i as u8, 'x')).unwrap();
+        }
+        assert_eq!(rx.len(), 2);
+        assert!(!tx.is_full());
+        let other_rx = rx;
+        assert_eq!(other_rx.try_recv(), Ok((0, 'x')));
+        assert_eq!(rx.try_recv(), Ok((1, 'x')));
+        assert!(rx.is_empty());
+        assert_eq!(rx.try_recv(), Err(RecvError));
+    }
+}
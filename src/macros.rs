@@ -0,0 +1,65 @@
+//! Constructor macros.
+
+/// Builds a pre-populated [`BareMetalDeque`](crate::BareMetalDeque).
+///
+/// `deque![a, b, c; cap = N]` holds the listed values, front first, in a deque of
+/// capacity `N`. Listing more than `N` values is a compile-time error.
+///
+/// `deque![value; N]` is a full deque of capacity `N` holding `N` copies of `value`.
+///
+/// ```
+/// use bare_metal_deque::deque;
+///
+/// let q = deque![1, 2, 3; cap = 8];
+/// assert_eq!((q.len(), q.front(), q.back()), (3, Some(1), Some(3)));
+///
+/// let zeros = deque![0u8; 16];
+/// assert!(zeros.is_full());
+/// ```
+///
+/// ```compile_fail
+/// let q = bare_metal_deque::deque![1, 2, 3; cap = 2];
+/// ```
+#[macro_export]
+macro_rules! deque {
+    ($($value:expr),* $(,)?; cap = $capacity:expr) => {{
+        const _: () = assert!(
+            <[&str]>::len(&[$(stringify!($value)),*]) <= $capacity,
+            "more values than the deque's capacity"
+        );
+        #[allow(unused_mut)]
+        let mut q = $crate::BareMetalDeque::<_, $capacity>::new();
+        $(q.push_back($value);)*
+        q
+    }};
+    ($fill:expr; $capacity:expr) => {{
+        let fill = $fill;
+        let mut q = $crate::BareMetalDeque::<_, $capacity>::new_with(fill);
+        while !q.is_full() {
+            q.push_back(fill);
+        }
+        q
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BareMetalDeque;
+
+    #[test]
+    fn deque_macro_test() {
+        let q: BareMetalDeque<i16, 4> = deque![-1, 2, 3, 4,; cap = 4];
+        assert!(q.is_full());
+        assert_eq!(q.iter().copied().collect::<Vec<_>>(), vec![-1, 2, 3, 4]);
+
+        let empty: BareMetalDeque<u8, 2> = deque![; cap = 2];
+        assert!(empty.is_empty());
+
+        let single = deque![7; cap = 3];
+        assert_eq!((single.len(), single.front()), (1, Some(7)));
+
+        let filled = deque![Some(1.5); 3];
+        assert_eq!(filled.iter().copied().collect::<Vec<_>>(), vec![Some(1.5); 3]);
+        assert!(deque![0; 0].is_empty());
+    }
+}
@@ -0,0 +1,87 @@
+//! Iterating over several sorted deques in one merged order, as when a telemetry packer
+//! drains per-sensor queues in global timestamp order. Merging needs no scratch buffer:
+//! each step compares the next element of every deque, so it suits a small number of
+//! deques.
+//!
+//! ```
+//! use bare_metal_deque::{deque, merge_iter_by_key};
+//!
+//! let imu = deque![(10, 'i'), (30, 'i'); cap = 4];
+//! let gps = deque![(20, 'g'), (40, 'g'); cap = 4];
+//! let baro = deque![(15, 'b'); cap = 4];
+//! let order: String = merge_iter_by_key([&imu, &gps, &baro], |(t, _)| *t).map(|(_, s)| s).collect();
+//! assert_eq!(order, "ibgig");
+//! ```
+
+use core::cmp::Ordering;
+
+use crate::{BareMetalDeque, DequeView};
+
+/// Merges sorted deques into one sorted iterator. Among equal elements, those from earlier
+/// deques in `deques` come first.
+pub fn merge_iter<T: Copy + Default + Ord, const N: usize, const K: usize>(
+    deques: [&BareMetalDeque<T, N>; K],
+) -> impl Iterator<Item = &T> {
+    merge_iter_by(deques, T::cmp)
+}
+
+/// Like `merge_iter()`, for deques sorted by `key`.
+pub fn merge_iter_by_key<T, const N: usize, const K: usize, U, F>(
+    deques: [&BareMetalDeque<T, N>; K],
+    mut key: F,
+) -> impl Iterator<Item = &T>
+where
+    T: Copy + Default,
+    U: Ord,
+    F: FnMut(&T) -> U,
+{
+    merge_iter_by(deques, move |a, b| key(a).cmp(&key(b)))
+}
+
+/// Like `merge_iter()`, for deques sorted by `compare`.
+pub fn merge_iter_by<'a, T, const N: usize, const K: usize, F>(
+    deques: [&'a BareMetalDeque<T, N>; K],
+    mut compare: F,
+) -> impl Iterator<Item = &'a T>
+where
+    T: Copy + Default,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let views: [DequeView<'a, T>; K] = deques.map(|deque| deque.view(..));
+    let mut positions = [0; K];
+    core::iter::from_fn(move || {
+        let mut best: Option<(usize, &'a T)> = None;
+        for (i, view) in views.iter().enumerate() {
+            if let Some(candidate) = view.get(positions[i])
+                && best.is_none_or(|(_, current)| compare(candidate, current) == Ordering::Less)
+            {
+                best = Some((i, candidate));
+            }
+        }
+        let (i, value) = best?;
+        positions[i] += 1;
+        Some(value)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_iter_test() {
+        let mut a = BareMetalDeque::<u8, 3>::new();
+        a.extend_from_slice(&[0, 0, 1]);
+        a.discard(2);
+        a.extend_from_slice(&[5, 9]);
+        let b = BareMetalDeque::<u8, 3>::try_init_with([2, 5, 6]).unwrap();
+        let empty = BareMetalDeque::<u8, 3>::new();
+        let merged: Vec<_> = merge_iter([&empty, &a, &b]).copied().collect();
+        assert_eq!(merged, vec![1, 2, 5, 5, 6, 9]);
+        let c = BareMetalDeque::<u8, 3>::try_init_with([7, 3]).unwrap();
+        let d = BareMetalDeque::<u8, 3>::try_init_with([8, 4, 1]).unwrap();
+        let descending: Vec<_> = merge_iter_by([&c, &d], |x, y| y.cmp(x)).copied().collect();
+        assert_eq!(descending, vec![8, 7, 4, 3, 1]);
+        assert_eq!(merge_iter::<u8, 3, 0>([]).count(), 0);
+    }
+}
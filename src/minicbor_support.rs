@@ -0,0 +1,69 @@
+//! [`minicbor`](https://docs.rs/minicbor) encoding of a deque's logical contents, behind
+//! the `minicbor` feature, so queued readings can go straight into CoAP or other CBOR
+//! payloads. A deque encodes as a definite-length CBOR array, front first. Decoding
+//! accepts definite or indefinite arrays and fails if one holds more elements than fit.
+//!
+//! ```
+//! use bare_metal_deque::BareMetalDeque;
+//!
+//! let mut q = BareMetalDeque::<u16, 4>::new();
+//! q.push_back(1);
+//! q.push_back(300);
+//!
+//! let mut payload = [0; 16];
+//! minicbor::encode(&q, payload.as_mut_slice()).unwrap();
+//! assert_eq!(&payload[..5], &[0x82, 0x01, 0x19, 0x01, 0x2C]);
+//!
+//! let received: BareMetalDeque<u16, 4> = minicbor::decode(&payload).unwrap();
+//! assert_eq!(received, q);
+//! ```
+
+use minicbor::decode::{self, Decoder};
+use minicbor::encode::{self, Encoder, Write};
+use minicbor::{Decode, Encode};
+
+use crate::BareMetalDeque;
+
+impl<C, T: Copy + Default + Encode<C>, const MAX_STORED: usize> Encode<C> for BareMetalDeque<T, MAX_STORED> {
+    fn encode<W: Write>(&self, e: &mut Encoder<W>, ctx: &mut C) -> Result<(), encode::Error<W::Error>> {
+        e.array(self.len() as u64)?;
+        for value in self.iter() {
+            value.encode(e, ctx)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'b, C, T: Copy + Default + Decode<'b, C>, const MAX_STORED: usize> Decode<'b, C> for BareMetalDeque<T, MAX_STORED> {
+    fn decode(d: &mut Decoder<'b>, ctx: &mut C) -> Result<Self, decode::Error> {
+        let mut result = Self::new();
+        for value in d.array_iter_with(ctx)? {
+            result
+                .try_push_back(value?)
+                .map_err(|_| decode::Error::message("array holds more elements than the deque's capacity"))?;
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapped_round_trip_test() {
+        let mut q = BareMetalDeque::<i32, 3>::new();
+        q.extend_from_slice(&[1, 2, 3]);
+        q.pop_front();
+        q.push_back(-4);
+        let mut buf = [0; 16];
+        minicbor::encode(q, buf.as_mut_slice()).unwrap();
+        let restored: BareMetalDeque<i32, 3> = minicbor::decode(&buf).unwrap();
+        assert_eq!(restored.view(..), [2, 3, -4]);
+        assert!(minicbor::decode::<BareMetalDeque<i32, 2>>(&buf).is_err());
+
+        // An indefinite-length array: [_ 7, 8]
+        let restored: BareMetalDeque<i32, 3> = minicbor::decode(&[0x9F, 0x07, 0x08, 0xFF]).unwrap();
+        assert_eq!(restored.view(..), [7, 8]);
+    }
+}
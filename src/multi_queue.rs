@@ -0,0 +1,162 @@
+//! Several independent FIFO queues sharing one pool of slots, for drivers that serve many
+//! low-rate channels. Slots are taken from the pool as elements arrive and returned as
+//! they leave, so the pool need only cover the total backlog expected at once rather than
+//! every channel's worst case. A per-queue quota keeps one busy channel from starving the
+//! others; quotas may add up to more than the pool.
+//!
+//! ```
+//! use bare_metal_deque::BareMetalMultiQueue;
+//!
+//! // Three channels that may each burst to 8 elements, sharing 12 slots instead of 3 x 8.
+//! let mut channels = BareMetalMultiQueue::<u8, 12, 3>::new([8, 8, 8]);
+//! channels.push(0, b'a').unwrap();
+//! channels.push(2, b'z').unwrap();
+//! assert_eq!(channels.pop(2), Some(b'z'));
+//! assert_eq!(channels.pop(1), None);
+//! assert_eq!(channels.len(0), 1);
+//! assert_eq!(channels.free_slots(), 11);
+//! ```
+
+use crate::CapacityError;
+
+#[derive(Copy, Clone, Debug)]
+pub struct BareMetalMultiQueue<T, const STORAGE: usize, const QUEUES: usize> {
+    array: [T; STORAGE],
+    /// For each slot, the next slot in its queue or in the free list, or `STORAGE` at the
+    /// end of a list.
+    next: [usize; STORAGE],
+    free_head: usize,
+    free_count: usize,
+    heads: [usize; QUEUES],
+    tails: [usize; QUEUES],
+    sizes: [usize; QUEUES],
+    quotas: [usize; QUEUES],
+}
+
+impl<T: Copy + Default, const STORAGE: usize, const QUEUES: usize> BareMetalMultiQueue<T, STORAGE, QUEUES> {
+    /// Creates empty queues, where queue `q` can hold up to `quotas[q]` elements while
+    /// free slots remain in the shared pool.
+    pub fn new(quotas: [usize; QUEUES]) -> Self {
+        Self {
+            array: [T::default(); STORAGE],
+            next: core::array::from_fn(|i| i + 1),
+            free_head: 0,
+            free_count: STORAGE,
+            heads: [STORAGE; QUEUES],
+            tails: [STORAGE; QUEUES],
+            sizes: [0; QUEUES],
+            quotas,
+        }
+    }
+
+    /// The most elements queue `q` can hold.
+    pub fn quota(&self, q: usize) -> usize {
+        self.quotas[q]
+    }
+
+    pub fn len(&self, q: usize) -> usize {
+        self.sizes[q]
+    }
+
+    pub fn is_empty(&self, q: usize) -> bool {
+        self.sizes[q] == 0
+    }
+
+    /// Whether queue `q` has used its quota.
+    pub fn is_full(&self, q: usize) -> bool {
+        self.sizes[q] >= self.quotas[q]
+    }
+
+    /// Number of unused slots in the shared pool.
+    pub fn free_slots(&self) -> usize {
+        self.free_count
+    }
+
+    /// Appends `value` to queue `q`, failing if the queue has used its quota or the pool
+    /// has no free slot. Panics if `q >= QUEUES`.
+    pub fn push(&mut self, q: usize, value: T) -> Result<(), CapacityError> {
+        if self.is_full(q) || self.free_count == 0 {
+            return Err(CapacityError);
+        }
+        let slot = self.free_head;
+        self.free_head = self.next[slot];
+        self.free_count -= 1;
+
+        self.array[slot] = value;
+        self.next[slot] = STORAGE;
+        if self.sizes[q] == 0 {
+            self.heads[q] = slot;
+        } else {
+            self.next[self.tails[q]] = slot;
+        }
+        self.tails[q] = slot;
+        self.sizes[q] += 1;
+        Ok(())
+    }
+
+    /// Removes the oldest element of queue `q`. Panics if `q >= QUEUES`.
+    pub fn pop(&mut self, q: usize) -> Option<T> {
+        let value = self.peek(q)?;
+        let slot = self.heads[q];
+        self.heads[q] = self.next[slot];
+        self.sizes[q] -= 1;
+
+        self.next[slot] = self.free_head;
+        self.free_head = slot;
+        self.free_count += 1;
+        Some(value)
+    }
+
+    /// The oldest element of queue `q`, without removing it. Panics if `q >= QUEUES`.
+    pub fn peek(&self, q: usize) -> Option<T> {
+        if self.is_empty(q) {
+            None
+        } else {
+            Some(self.array[self.heads[q]])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn independent_queues_test() {
+        let mut mq = BareMetalMultiQueue::<u16, 6, 3>::new([3, 0, 2]);
+        assert_eq!(mq.push(1, 9), Err(CapacityError));
+        for round in 0..5 {
+            for x in 0..3 {
+                assert_eq!(mq.push(0, round * 10 + x), Ok(()));
+            }
+            assert_eq!(mq.push(0, 99), Err(CapacityError));
+            assert_eq!(mq.push(2, round), Ok(()));
+            assert_eq!(mq.pop(0), Some(round * 10));
+            assert_eq!(mq.pop(0), Some(round * 10 + 1));
+            assert_eq!(mq.pop(0), Some(round * 10 + 2));
+            assert_eq!(mq.pop(2), Some(round));
+        }
+        assert!(mq.is_empty(0) && mq.is_empty(2));
+        assert_eq!(mq.quota(2), 2);
+    }
+
+    #[test]
+    fn shared_pool_test() {
+        let mut mq = BareMetalMultiQueue::<u8, 4, 2>::new([3, 3]);
+        for x in 0..3 {
+            mq.push(0, x).unwrap();
+        }
+        mq.push(1, 10).unwrap();
+        assert!(!mq.is_full(1));
+        assert_eq!(mq.push(1, 11), Err(CapacityError));
+
+        assert_eq!(mq.pop(0), Some(0));
+        mq.push(1, 11).unwrap();
+        assert_eq!(mq.free_slots(), 0);
+        assert_eq!(mq.pop(1), Some(10));
+        assert_eq!(mq.pop(0), Some(1));
+        assert_eq!(mq.pop(1), Some(11));
+        assert_eq!(mq.pop(0), Some(2));
+        assert_eq!(mq.free_slots(), 4);
+    }
+}
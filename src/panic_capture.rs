@@ -0,0 +1,177 @@
+//! Capture of the last panic message, for field devices that need to report why they
+//! last crashed. A `#[panic_handler]` records the panic into a [`PanicCapture`], and
+//! after the reset, startup code reads it back with [`PanicCapture::take`].
+//!
+//! To survive a reset the capture must live in RAM that startup code does not zero,
+//! typically a `static` placed in a `.uninit` linker section and declared as a
+//! `MaybeUninit`. Such memory holds garbage after a cold boot, so the capture is marked
+//! with a magic number and a CRC. [`PanicCapture::from_uninit`] checks the header before
+//! handing out a reference, and `take()` only reports contents that pass both checks.
+//!
+//! ```no_run
+//! use core::mem::MaybeUninit;
+//! use core::ptr::addr_of_mut;
+//! use bare_metal_deque::PanicCapture;
+//!
+//! #[unsafe(link_section = ".uninit.panic")]
+//! static mut PANIC: MaybeUninit<PanicCapture<128>> = MaybeUninit::uninit();
+//!
+//! // Called once at startup, so this is the only reference to PANIC.
+//! let capture = PanicCapture::from_uninit(unsafe { &mut *addr_of_mut!(PANIC) });
+//! let mut message = [0; 128];
+//! if let Some(len) = capture.take(&mut message) {
+//!     // Report &message[..len].
+//! }
+//! ```
+//!
+//! ```
+//! use bare_metal_deque::PanicCapture;
+//!
+//! let mut capture = PanicCapture::<64>::new();
+//! // In the panic handler: capture.record(info);
+//! capture.record_fmt(format_args!("panicked at src/main.rs:10:5:\nsensor timeout"));
+//!
+//! // After reboot:
+//! let mut message = [0; 64];
+//! let len = capture.take(&mut message).unwrap();
+//! assert_eq!(&message[..len], b"panicked at src/main.rs:10:5:\nsensor timeout");
+//! assert_eq!(capture.take(&mut message), None);
+//! ```
+
+use core::fmt::{self, Write};
+use core::mem::MaybeUninit;
+use core::panic::PanicInfo;
+
+use crate::ByteDeque;
+use crate::crc::{Crc, Crc32};
+
+const MAGIC: u32 = 0x5041_4E43;
+
+#[derive(Copy, Clone, Debug)]
+pub struct PanicCapture<const N: usize> {
+    magic: u32,
+    checksum: u32,
+    message: ByteDeque<N>,
+}
+
+impl<const N: usize> Default for PanicCapture<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> PanicCapture<N> {
+    pub const fn new() -> Self {
+        Self { magic: 0, checksum: 0, message: ByteDeque::new_with(0) }
+    }
+
+    /// Takes a capture in memory that was not initialized at startup, keeping it if its
+    /// header is intact and initializing it empty otherwise.
+    pub fn from_uninit(slot: &'static mut MaybeUninit<Self>) -> &'static mut Self {
+        let ptr = slot.as_mut_ptr();
+        // SAFETY: the header fields are plain integers, read through raw pointers without
+        // creating a reference to a possibly invalid capture. Like all code using
+        // `.uninit` RAM, this assumes the memory holds some fixed bit pattern, left by the
+        // previous boot or by power-up, rather than bytes Rust considers uninitialized.
+        let (magic, start, size) = unsafe {
+            (
+                (&raw const (*ptr).magic).read_volatile(),
+                (&raw const (*ptr).message.start).read_volatile(),
+                (&raw const (*ptr).message.size).read_volatile(),
+            )
+        };
+        if magic != MAGIC || start >= N.max(1) || size > N {
+            return slot.write(Self::new());
+        }
+        // SAFETY: under the same assumption, the header is valid and every other field
+        // is an integer or a byte, for which any bit pattern is a valid value.
+        unsafe { slot.assume_init_mut() }
+    }
+
+    /// Records the panic's location and message, truncated to `N` bytes.
+    pub fn record(&mut self, info: &PanicInfo) {
+        self.record_fmt(format_args!("{}", info));
+    }
+
+    /// Records an arbitrary message, truncated to `N` bytes.
+    pub fn record_fmt(&mut self, args: fmt::Arguments) {
+        self.message = ByteDeque::new_with(0);
+        // Writing stops with an error once the buffer is full, keeping what fit.
+        let _ = self.message.write_fmt(args);
+        self.checksum = self.compute_checksum();
+        self.magic = MAGIC;
+    }
+
+    /// Whether a recorded message is present and intact.
+    pub fn is_valid(&self) -> bool {
+        self.magic == MAGIC
+            && self.message.check_invariants().is_ok()
+            && self.checksum == self.compute_checksum()
+    }
+
+    /// Copies a valid recorded message into `dst` and clears the capture, returning the
+    /// number of bytes copied. Returns `None` if there is no valid message.
+    pub fn take(&mut self, dst: &mut [u8]) -> Option<usize> {
+        if !self.is_valid() {
+            self.clear();
+            return None;
+        }
+        let len = self.message.peek(dst);
+        self.clear();
+        Some(len)
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    fn compute_checksum(&self) -> u32 {
+        let len = (self.message.size as u32).to_le_bytes();
+        Crc32::checksum(len.iter().chain(self.message.iter()).copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validation_test() {
+        let mut capture = PanicCapture::<8>::new();
+        let mut message = [0; 8];
+        assert!(!capture.is_valid());
+        assert_eq!(capture.take(&mut message), None);
+
+        capture.record_fmt(format_args!("overflow at {}", 42));
+        assert!(capture.is_valid());
+        capture.message[3] ^= 0x20;
+        assert!(!capture.is_valid());
+        assert_eq!(capture.take(&mut message), None);
+
+        capture.record_fmt(format_args!("overflow at {}", 42));
+        assert_eq!(capture.take(&mut message), Some(8));
+        assert_eq!(&message, b"overflow");
+
+        capture.magic = MAGIC;
+        capture.message.size = 9;
+        assert!(!capture.is_valid());
+    }
+
+    #[test]
+    fn from_uninit_test() {
+        let mut before_reset = PanicCapture::<8>::new();
+        before_reset.record_fmt(format_args!("stack"));
+        let slot = Box::leak(Box::new(MaybeUninit::new(before_reset)));
+        let mut message = [0; 8];
+        assert_eq!(PanicCapture::from_uninit(slot).take(&mut message), Some(5));
+
+        let garbage = Box::leak(Box::new(MaybeUninit::<PanicCapture<8>>::uninit()));
+        unsafe {
+            garbage.as_mut_ptr().cast::<u8>().write_bytes(0xA5, size_of::<PanicCapture<8>>());
+            (&raw mut (*garbage.as_mut_ptr()).magic).write(MAGIC);
+        }
+        let capture = PanicCapture::from_uninit(garbage);
+        assert!(!capture.is_valid());
+        assert_eq!(capture.take(&mut message), None);
+    }
+}
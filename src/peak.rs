@@ -0,0 +1,148 @@
+//! Peak and valley detection over a sliding window of samples, for pulse counting
+//! (flow meters, heartbeats, etc.).
+//!
+//! A sample is reported once `WINDOW / 2` newer samples have arrived, if it is the
+//! most extreme value in the window, lies beyond the threshold, and is at least
+//! `min_separation` samples after the previously reported one.
+//!
+//! ```
+//! use bare_metal_deque::PeakDetector;
+//!
+//! let mut detector = PeakDetector::<i32, 3>::peaks(5, 2);
+//! let signal = [0, 3, 9, 2, 0, 7, 1, 0];
+//! let found: Vec<_> = signal.iter().filter_map(|s| detector.push(*s)).collect();
+//! assert_eq!(found.len(), 2);
+//! assert_eq!((found[0].index, found[0].value), (2, 9));
+//! assert_eq!((found[1].index, found[1].value), (5, 7));
+//! ```
+
+use crate::BareMetalDeque;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Extremum {
+    Peak,
+    Valley,
+}
+
+/// A detected extremum: its value and the zero-based index of the sample in the stream.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Detection<T> {
+    pub kind: Extremum,
+    pub value: T,
+    pub index: u32,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct PeakDetector<T: Default, const WINDOW: usize> {
+    window: BareMetalDeque<T, WINDOW>,
+    kind: Extremum,
+    threshold: T,
+    min_separation: u32,
+    samples_seen: u32,
+    last_detection: Option<u32>,
+}
+
+impl<T: Copy + Default + PartialOrd, const WINDOW: usize> PeakDetector<T, WINDOW> {
+    /// Detects local maxima strictly greater than `threshold`.
+    pub fn peaks(threshold: T, min_separation: u32) -> Self {
+        Self::new(Extremum::Peak, threshold, min_separation)
+    }
+
+    /// Detects local minima strictly less than `threshold`.
+    pub fn valleys(threshold: T, min_separation: u32) -> Self {
+        Self::new(Extremum::Valley, threshold, min_separation)
+    }
+
+    fn new(kind: Extremum, threshold: T, min_separation: u32) -> Self {
+        if WINDOW == 0 {
+            panic!("PeakDetector window must be nonempty");
+        }
+        Self {
+            window: BareMetalDeque::new(),
+            kind,
+            threshold,
+            min_separation,
+            samples_seen: 0,
+            last_detection: None,
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::new(self.kind, self.threshold, self.min_separation);
+    }
+
+    /// Adds a sample, returning the extremum it confirms, if any.
+    pub fn push(&mut self, sample: T) -> Option<Detection<T>> {
+        if self.window.is_full() {
+            self.window.pop_front();
+        }
+        self.window.push_back(sample);
+        self.samples_seen = self.samples_seen.wrapping_add(1);
+        if !self.window.is_full() {
+            return None;
+        }
+
+        let center = WINDOW / 2;
+        let value = self.window[center];
+        if !self.beyond(value, self.threshold) {
+            return None;
+        }
+        for (i, other) in self.window.iter().enumerate() {
+            // Ties go to the earliest sample so that plateaus report only once.
+            let dominated = if i < center {
+                !self.beyond(value, *other)
+            } else {
+                i > center && self.beyond(*other, value)
+            };
+            if dominated {
+                return None;
+            }
+        }
+
+        let index = self.samples_seen.wrapping_sub((WINDOW - center) as u32);
+        if let Some(last) = self.last_detection
+            && index.wrapping_sub(last) < self.min_separation
+        {
+            return None;
+        }
+        self.last_detection = Some(index);
+        Some(Detection { kind: self.kind, value, index })
+    }
+
+    fn beyond(&self, a: T, b: T) -> bool {
+        match self.kind {
+            Extremum::Peak => a > b,
+            Extremum::Valley => a < b,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn peak_test() {
+        let signal = [0, 10, 0, 10, 0, 0, 10, 10, 0, 4, 0];
+        let mut detector = PeakDetector::<i32, 3>::peaks(5, 3);
+        let found: Vec<u32> = signal.iter().filter_map(|s| detector.push(*s)).map(|d| d.index).collect();
+        assert_eq!(found, vec![1, 6]);
+
+        detector.reset();
+        assert_eq!(detector.push(0), None);
+        assert_eq!(detector.push(10), None);
+        assert_eq!(detector.push(0).map(|d| d.index), Some(1));
+
+        let mut detector = PeakDetector::<i32, 3>::peaks(5, 1);
+        let found: Vec<u32> = signal.iter().filter_map(|s| detector.push(*s)).map(|d| d.index).collect();
+        assert_eq!(found, vec![1, 3, 6]);
+    }
+
+    #[test]
+    fn valley_test() {
+        let signal = [5, 1, 5, 5, -3, 5, 5];
+        let mut detector = PeakDetector::<i32, 5>::valleys(0, 1);
+        let found: Vec<Detection<i32>> = signal.iter().filter_map(|s| detector.push(*s)).collect();
+        assert_eq!(found, vec![Detection { kind: Extremum::Valley, value: -3, index: 4 }]);
+    }
+}
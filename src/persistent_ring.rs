@@ -0,0 +1,321 @@
+//! A circular record log stored in erase-block flash, so event logs survive a reboot.
+//!
+//! The storage is divided into sectors (erase blocks) that are filled in order. Each
+//! sector begins with a header holding a sequence number, which `mount()` uses to find
+//! the newest sector after a reboot. Records never span sectors; when the current sector
+//! cannot hold the next record, the following sector is erased and reused, discarding
+//! the oldest records.
+//!
+//! Each record is stored as its length (`u16`, little-endian), a CRC-16 of its payload,
+//! and the payload itself. A record torn by power loss fails its CRC and is skipped when
+//! reading.
+//!
+//! ```
+//! use bare_metal_deque::{PersistentRing, Storage};
+//!
+//! struct RamFlash([u8; 256]);
+//!
+//! impl Storage for RamFlash {
+//!     type Error = ();
+//!     const SECTOR_SIZE: usize = 64;
+//!
+//!     fn sector_count(&self) -> usize {
+//!         self.0.len() / Self::SECTOR_SIZE
+//!     }
+//!
+//!     fn read(&mut self, offset: usize, buf: &mut [u8]) -> Result<(), ()> {
+//!         buf.copy_from_slice(&self.0[offset..offset + buf.len()]);
+//!         Ok(())
+//!     }
+//!
+//!     fn write(&mut self, offset: usize, data: &[u8]) -> Result<(), ()> {
+//!         self.0[offset..offset + data.len()].copy_from_slice(data);
+//!         Ok(())
+//!     }
+//!
+//!     fn erase(&mut self, sector: usize) -> Result<(), ()> {
+//!         self.0[sector * Self::SECTOR_SIZE..(sector + 1) * Self::SECTOR_SIZE].fill(0xFF);
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let mut log = PersistentRing::mount(RamFlash([0xFF; 256])).unwrap();
+//! log.append(b"boot").unwrap();
+//! log.append(b"brownout").unwrap();
+//!
+//! // After a reboot:
+//! let mut log = PersistentRing::mount(log.into_storage()).unwrap();
+//! let mut count = 0;
+//! log.for_each_record(&mut [0; 64], |_| count += 1).unwrap();
+//! assert_eq!(count, 2);
+//! ```
+
+use crate::crc::{Crc, Crc16};
+
+const SECTOR_MAGIC: u32 = 0x474F_4C50;
+const SECTOR_HEADER_LEN: usize = 8;
+const RECORD_HEADER_LEN: usize = 4;
+const ERASED_LEN: u16 = 0xFFFF;
+
+/// Erase-block storage such as external NOR flash. Erased bytes read as `0xFF`.
+pub trait Storage {
+    type Error;
+
+    /// Size of an erase block in bytes.
+    const SECTOR_SIZE: usize;
+
+    fn sector_count(&self) -> usize;
+
+    /// Reads `buf.len()` bytes starting at byte `offset`.
+    fn read(&mut self, offset: usize, buf: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Programs `data` starting at byte `offset`, which lies in previously erased space.
+    fn write(&mut self, offset: usize, data: &[u8]) -> Result<(), Self::Error>;
+
+    /// Erases sector number `sector`.
+    fn erase(&mut self, sector: usize) -> Result<(), Self::Error>;
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PersistentError<E> {
+    Storage(E),
+    /// The storage has fewer than two sectors, or sectors too small for any record.
+    BadGeometry,
+    /// The record is longer than a sector can hold.
+    RecordTooLarge,
+    /// A stored record is longer than the buffer provided for reading it.
+    BufferTooSmall,
+}
+
+impl<E> From<E> for PersistentError<E> {
+    fn from(e: E) -> Self {
+        PersistentError::Storage(e)
+    }
+}
+
+pub struct PersistentRing<S: Storage> {
+    storage: S,
+    head: usize,
+    head_seq: u32,
+    write_offset: usize,
+}
+
+impl<S: Storage> PersistentRing<S> {
+    /// Opens the log on `storage`, formatting it if it holds no valid sectors.
+    pub fn mount(mut storage: S) -> Result<Self, PersistentError<S::Error>> {
+        if storage.sector_count() < 2 || S::SECTOR_SIZE <= SECTOR_HEADER_LEN + RECORD_HEADER_LEN {
+            return Err(PersistentError::BadGeometry);
+        }
+        let mut newest = None;
+        for sector in 0..storage.sector_count() {
+            if let Some(seq) = read_sector_seq(&mut storage, sector)?
+                && newest.is_none_or(|(_, newest_seq): (usize, u32)| seq.wrapping_sub(newest_seq) as i32 > 0)
+            {
+                newest = Some((sector, seq));
+            }
+        }
+        match newest {
+            None => Self::format(storage),
+            Some((head, head_seq)) => {
+                let mut ring = Self { storage, head, head_seq, write_offset: SECTOR_HEADER_LEN };
+                ring.write_offset = ring.scan_sector(head, &mut [], |_| {})?;
+                Ok(ring)
+            }
+        }
+    }
+
+    /// Erases the whole log.
+    pub fn format(mut storage: S) -> Result<Self, PersistentError<S::Error>> {
+        for sector in 0..storage.sector_count() {
+            storage.erase(sector)?;
+        }
+        let mut ring = Self { storage, head: 0, head_seq: 0, write_offset: SECTOR_HEADER_LEN };
+        ring.write_sector_header()?;
+        Ok(ring)
+    }
+
+    pub fn into_storage(self) -> S {
+        self.storage
+    }
+
+    /// Largest record that can be appended.
+    pub fn max_record_len(&self) -> usize {
+        (S::SECTOR_SIZE - SECTOR_HEADER_LEN - RECORD_HEADER_LEN).min(ERASED_LEN as usize - 1)
+    }
+
+    /// Appends a record, erasing the oldest sector if necessary to make room.
+    pub fn append(&mut self, record: &[u8]) -> Result<(), PersistentError<S::Error>> {
+        if record.len() > self.max_record_len() {
+            return Err(PersistentError::RecordTooLarge);
+        }
+        let needed = RECORD_HEADER_LEN + record.len();
+        if self.write_offset + needed > S::SECTOR_SIZE {
+            self.head = (self.head + 1) % self.storage.sector_count();
+            self.head_seq = self.head_seq.wrapping_add(1);
+            self.storage.erase(self.head)?;
+            self.write_sector_header()?;
+            self.write_offset = SECTOR_HEADER_LEN;
+        }
+        let mut header = [0; RECORD_HEADER_LEN];
+        header[..2].copy_from_slice(&(record.len() as u16).to_le_bytes());
+        header[2..].copy_from_slice(&(Crc16::checksum(record.iter().copied()) as u16).to_le_bytes());
+        let offset = self.head * S::SECTOR_SIZE + self.write_offset;
+        self.storage.write(offset, &header)?;
+        self.storage.write(offset + RECORD_HEADER_LEN, record)?;
+        self.write_offset += needed;
+        Ok(())
+    }
+
+    /// Reads each intact record into `buf` and passes it to `visit`, oldest first.
+    pub fn for_each_record<F: FnMut(&[u8])>(&mut self, buf: &mut [u8], mut visit: F) -> Result<(), PersistentError<S::Error>> {
+        let sectors = self.storage.sector_count();
+        for i in 1..=sectors {
+            let sector = (self.head + i) % sectors;
+            if read_sector_seq(&mut self.storage, sector)?.is_some() {
+                self.scan_sector(sector, buf, &mut visit)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Visits the records of `sector` whose payloads fit in `buf`, returning the offset just
+    /// past the last stored record. With an empty `buf`, only finds the offset.
+    fn scan_sector<F: FnMut(&[u8])>(&mut self, sector: usize, buf: &mut [u8], mut visit: F) -> Result<usize, PersistentError<S::Error>> {
+        let base = sector * S::SECTOR_SIZE;
+        let mut offset = SECTOR_HEADER_LEN;
+        while offset + RECORD_HEADER_LEN <= S::SECTOR_SIZE {
+            let mut header = [0; RECORD_HEADER_LEN];
+            self.storage.read(base + offset, &mut header)?;
+            let len = u16::from_le_bytes([header[0], header[1]]);
+            if len == ERASED_LEN {
+                break;
+            }
+            let end = offset + RECORD_HEADER_LEN + len as usize;
+            if end > S::SECTOR_SIZE {
+                // A corrupt length; nothing after it can be located.
+                return Ok(S::SECTOR_SIZE);
+            }
+            if !buf.is_empty() {
+                let payload = buf.get_mut(..len as usize).ok_or(PersistentError::BufferTooSmall)?;
+                self.storage.read(base + offset + RECORD_HEADER_LEN, payload)?;
+                let crc = u16::from_le_bytes([header[2], header[3]]);
+                if Crc16::checksum(payload.iter().copied()) as u16 == crc {
+                    visit(payload);
+                }
+            }
+            offset = end;
+        }
+        Ok(offset)
+    }
+
+    fn write_sector_header(&mut self) -> Result<(), PersistentError<S::Error>> {
+        let mut header = [0; SECTOR_HEADER_LEN];
+        header[..4].copy_from_slice(&SECTOR_MAGIC.to_le_bytes());
+        header[4..].copy_from_slice(&self.head_seq.to_le_bytes());
+        self.storage.write(self.head * S::SECTOR_SIZE, &header)?;
+        Ok(())
+    }
+}
+
+fn read_sector_seq<S: Storage>(storage: &mut S, sector: usize) -> Result<Option<u32>, S::Error> {
+    let mut header = [0; SECTOR_HEADER_LEN];
+    storage.read(sector * S::SECTOR_SIZE, &mut header)?;
+    if header[..4] == SECTOR_MAGIC.to_le_bytes() {
+        Ok(Some(u32::from_le_bytes([header[4], header[5], header[6], header[7]])))
+    } else {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Flash simulation in which programming can only clear bits.
+    struct NorFlash([u8; 128]);
+
+    impl Storage for NorFlash {
+        type Error = ();
+        const SECTOR_SIZE: usize = 32;
+
+        fn sector_count(&self) -> usize {
+            self.0.len() / Self::SECTOR_SIZE
+        }
+
+        fn read(&mut self, offset: usize, buf: &mut [u8]) -> Result<(), ()> {
+            buf.copy_from_slice(&self.0[offset..offset + buf.len()]);
+            Ok(())
+        }
+
+        fn write(&mut self, offset: usize, data: &[u8]) -> Result<(), ()> {
+            for (cell, b) in self.0[offset..offset + data.len()].iter_mut().zip(data) {
+                *cell &= *b;
+            }
+            Ok(())
+        }
+
+        fn erase(&mut self, sector: usize) -> Result<(), ()> {
+            self.0[sector * Self::SECTOR_SIZE..(sector + 1) * Self::SECTOR_SIZE].fill(0xFF);
+            Ok(())
+        }
+    }
+
+    fn records(ring: &mut PersistentRing<NorFlash>) -> Vec<Vec<u8>> {
+        let mut found = Vec::new();
+        ring.for_each_record(&mut [0; 32], |r| found.push(r.to_vec())).unwrap();
+        found
+    }
+
+    #[test]
+    fn wrap_and_remount_test() {
+        let mut ring = PersistentRing::mount(NorFlash([0x00; 128])).unwrap();
+        assert!(records(&mut ring).is_empty());
+        assert_eq!(ring.append(&[0; 21]), Err(PersistentError::RecordTooLarge));
+
+        // Two 10-byte records fill a 32-byte sector after its 8-byte header.
+        for i in 0..9u8 {
+            ring.append(&[i; 6]).unwrap();
+        }
+        let expected: Vec<Vec<u8>> = (2..9u8).map(|i| vec![i; 6]).collect();
+        assert_eq!(records(&mut ring), expected);
+
+        let mut ring = PersistentRing::mount(ring.into_storage()).unwrap();
+        assert_eq!(records(&mut ring), expected);
+        ring.append(&[9; 6]).unwrap();
+        let mut ring = PersistentRing::mount(ring.into_storage()).unwrap();
+        ring.append(&[10; 6]).unwrap();
+        let expected: Vec<Vec<u8>> = (4..11u8).map(|i| vec![i; 6]).collect();
+        assert_eq!(records(&mut ring), expected);
+        assert_eq!(ring.for_each_record(&mut [0; 4], |_| {}), Err(PersistentError::BufferTooSmall));
+    }
+
+    #[test]
+    fn sequence_wrap_test() {
+        let mut ring = PersistentRing::mount(NorFlash([0xFF; 128])).unwrap();
+        ring.head_seq = u32::MAX - 1;
+        ring.storage.erase(0).unwrap();
+        ring.write_sector_header().unwrap();
+        // Fills sectors numbered u32::MAX - 1, u32::MAX and 0, then starts sector 1.
+        for i in 0..7u8 {
+            ring.append(&[i; 6]).unwrap();
+        }
+        let mut ring = PersistentRing::mount(ring.into_storage()).unwrap();
+        ring.append(&[7; 6]).unwrap();
+        let expected: Vec<Vec<u8>> = (0..8u8).map(|i| vec![i; 6]).collect();
+        assert_eq!(records(&mut ring), expected);
+    }
+
+    #[test]
+    fn torn_record_test() {
+        let mut ring = PersistentRing::mount(NorFlash([0xFF; 128])).unwrap();
+        ring.append(b"first").unwrap();
+        ring.append(b"second").unwrap();
+        let mut flash = ring.into_storage();
+        // Simulate power loss partway through programming the second payload.
+        flash.0[8 + 4 + 5 + 4 + 3..32].fill(0xFF);
+
+        let mut ring = PersistentRing::mount(flash).unwrap();
+        ring.append(b"third").unwrap();
+        assert_eq!(records(&mut ring), vec![b"first".to_vec(), b"third".to_vec()]);
+    }
+}
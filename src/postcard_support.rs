@@ -0,0 +1,70 @@
+//! [`postcard`](https://docs.rs/postcard) encoding of a deque's logical contents, behind
+//! the `postcard` feature, so a queue of readings can be sent over a radio link in one
+//! call and rebuilt on the receiver. The wire format is postcard's encoding of a
+//! sequence: a varint element count followed by the elements, front first.
+//!
+//! ```
+//! use bare_metal_deque::BareMetalDeque;
+//!
+//! let mut q = BareMetalDeque::<u16, 4>::new();
+//! q.push_back(1);
+//! q.push_back(300);
+//!
+//! let mut packet = [0; 16];
+//! let encoded = q.to_postcard(&mut packet).unwrap();
+//! assert_eq!(encoded, &[2, 1, 0xAC, 0x02]);
+//!
+//! let received = BareMetalDeque::<u16, 4>::from_postcard(encoded).unwrap();
+//! assert_eq!(received.iter().copied().collect::<Vec<_>>(), vec![1, 300]);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::BareMetalDeque;
+
+impl<T: Copy + Default, const MAX_STORED: usize> BareMetalDeque<T, MAX_STORED> {
+    /// Encodes the contents into the start of `buf`, returning the encoded portion.
+    pub fn to_postcard<'b>(&self, buf: &'b mut [u8]) -> postcard::Result<&'b mut [u8]>
+    where
+        T: Serialize,
+    {
+        postcard::to_slice(self, buf)
+    }
+
+    /// Decodes contents encoded by `to_postcard()`. Fails if the encoding holds more
+    /// elements than fit.
+    pub fn from_postcard<'de>(bytes: &'de [u8]) -> postcard::Result<Self>
+    where
+        T: Deserialize<'de>,
+    {
+        postcard::from_bytes(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Copy, Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+    struct Reading {
+        sensor: u8,
+        value: i32,
+    }
+
+    #[test]
+    fn struct_round_trip_test() {
+        let mut q = BareMetalDeque::<Reading, 3>::new();
+        for (sensor, value) in [(1, -5), (2, 70000), (3, 0)] {
+            q.push_back(Reading { sensor, value });
+        }
+        q.pop_front();
+        q.push_back(Reading { sensor: 4, value: 1 });
+
+        let mut buf = [0; 32];
+        assert!(q.to_postcard(&mut buf[..3]).is_err());
+        let encoded = q.to_postcard(&mut buf).unwrap();
+        let decoded = BareMetalDeque::<Reading, 3>::from_postcard(encoded).unwrap();
+        assert_eq!(decoded.iter().copied().collect::<Vec<_>>(), q.iter().copied().collect::<Vec<_>>());
+        assert!(BareMetalDeque::<Reading, 2>::from_postcard(encoded).is_err());
+    }
+}
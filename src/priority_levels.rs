@@ -0,0 +1,117 @@
+//! A queue per priority level, serviced highest level first, as in CAN or radio transmit
+//! scheduling. Level 0 is the highest priority.
+//!
+//! Strict priority lets a busy high level starve the levels below it. A weighted queue
+//! instead lets each level be serviced at most its weight in a row while a lower level
+//! is waiting, before the lower level gets a turn.
+//!
+//! ```
+//! use bare_metal_deque::PriorityLevels;
+//!
+//! let mut tx = PriorityLevels::<&str, 4, 2>::new();
+//! tx.push(1, "telemetry").unwrap();
+//! tx.push(0, "alarm").unwrap();
+//! assert_eq!(tx.pop_highest(), Some((0, "alarm")));
+//! assert_eq!(tx.pop_highest(), Some((1, "telemetry")));
+//! assert_eq!(tx.pop_highest(), None);
+//! ```
+
+use crate::{BareMetalDeque, CapacityError};
+
+#[derive(Copy, Clone, Debug)]
+pub struct PriorityLevels<T: Default, const N: usize, const LEVELS: usize> {
+    levels: [BareMetalDeque<T, N>; LEVELS],
+    weights: Option<[u8; LEVELS]>,
+    /// Turns each level may still take while lower levels wait.
+    credits: [u8; LEVELS],
+}
+
+impl<T: Copy + Default, const N: usize, const LEVELS: usize> Default for PriorityLevels<T, N, LEVELS> {
+    fn default() -> Self {
+        Self { levels: [BareMetalDeque::new(); LEVELS], weights: None, credits: [0; LEVELS] }
+    }
+}
+
+impl<T: Copy + Default, const N: usize, const LEVELS: usize> PriorityLevels<T, N, LEVELS> {
+    /// Creates a strict-priority queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a weighted queue, in which level `l` is serviced at most `weights[l]` times
+    /// in a row while a lower level has elements waiting. Panics if a weight is 0.
+    pub fn weighted(weights: [u8; LEVELS]) -> Self {
+        if weights.contains(&0) {
+            panic!("Priority weights must be nonzero");
+        }
+        Self { weights: Some(weights), credits: weights, ..Self::new() }
+    }
+
+    /// Appends `value` to level `level`, failing if that level is full. Panics if
+    /// `level >= LEVELS`.
+    pub fn push(&mut self, level: usize, value: T) -> Result<(), CapacityError> {
+        self.levels[level].try_push_back(value)
+    }
+
+    /// Removes the oldest element of the highest-priority level that is due service,
+    /// returning it with its level.
+    pub fn pop_highest(&mut self) -> Option<(usize, T)> {
+        let waiting = |l: &usize| !self.levels[*l].is_empty();
+        let level = match self.weights {
+            None => (0..LEVELS).find(waiting)?,
+            Some(weights) => {
+                let first = (0..LEVELS).find(waiting)?;
+                let level = (first..LEVELS).filter(waiting).find(|l| self.credits[*l] > 0);
+                let level = level.unwrap_or_else(|| {
+                    self.credits = weights;
+                    first
+                });
+                self.credits[level] -= 1;
+                self.credits[..level].copy_from_slice(&weights[..level]);
+                level
+            }
+        };
+        self.levels[level].pop_front().map(|value| (level, value))
+    }
+
+    /// The elements waiting at `level`. Panics if `level >= LEVELS`.
+    pub fn level(&self, level: usize) -> &BareMetalDeque<T, N> {
+        &self.levels[level]
+    }
+
+    /// Total number of elements waiting at all levels.
+    pub fn len(&self) -> usize {
+        self.levels.iter().map(BareMetalDeque::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels.iter().all(BareMetalDeque::is_empty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain<const LEVELS: usize>(q: &mut PriorityLevels<u8, 8, LEVELS>) -> Vec<usize> {
+        core::iter::from_fn(|| q.pop_highest()).map(|(level, _)| level).collect()
+    }
+
+    #[test]
+    fn weighted_fairness_test() {
+        let mut strict = PriorityLevels::<u8, 8, 2>::new();
+        let mut weighted = PriorityLevels::<u8, 8, 3>::weighted([2, 1, 1]);
+        for x in 0..4 {
+            strict.push(1, x).unwrap();
+            strict.push(0, x).unwrap();
+            weighted.push(0, x).unwrap();
+            weighted.push(1, x).unwrap();
+        }
+        weighted.push(2, 0).unwrap();
+        assert_eq!(strict.len(), 8);
+        assert_eq!(drain(&mut strict), vec![0, 0, 0, 0, 1, 1, 1, 1]);
+        assert_eq!(drain(&mut weighted), vec![0, 0, 1, 0, 0, 2, 1, 1, 1]);
+        assert!(weighted.is_empty());
+        assert_eq!(PriorityLevels::<u8, 1, 1>::new().push(0, 1), Ok(()));
+    }
+}
@@ -0,0 +1,52 @@
+//! [proptest](https://docs.rs/proptest) strategies for deques, behind the `proptest` feature.
+//!
+//! ```
+//! use proptest::prelude::*;
+//!
+//! proptest!(|(mut q in bare_metal_deque::proptest::deque::<_, _, 8>(any::<u8>()))| {
+//!     let first = q.front();
+//!     prop_assert_eq!(q.pop_front(), first);
+//! });
+//! ```
+
+use core::fmt::Debug;
+
+use ::proptest::collection::vec;
+use ::proptest::strategy::Strategy;
+
+use crate::BareMetalDeque;
+
+/// Generates deques holding anywhere from zero to `MAX_STORED` values drawn from
+/// `element`, starting at any position in the ring so that wrapped layouts are covered.
+/// Shrinks toward short, unwrapped deques.
+pub fn deque<T, S, const MAX_STORED: usize>(element: S) -> impl Strategy<Value = BareMetalDeque<T, MAX_STORED>>
+where
+    T: Copy + Clone + Default + Debug,
+    S: Strategy<Value = T>,
+{
+    (0..MAX_STORED.max(1), vec(element, 0..=MAX_STORED)).prop_map(|(start, values)| {
+        let mut result = BareMetalDeque::new();
+        result.start = start % MAX_STORED.max(1);
+        for value in values {
+            result.push_back(value);
+        }
+        result
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn generated_deques_are_consistent(q in deque::<_, _, 5>(any::<i16>())) {
+            prop_assert!(q.start < 5);
+            prop_assert!(q.len() <= 5);
+            let (front, back) = q.as_slices();
+            prop_assert_eq!(front.len() + back.len(), q.len());
+            prop_assert!(q.iter().eq(front.iter().chain(back)));
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! Constant-memory quantile estimation with the P² algorithm (Jain and Chlamtac, 1985),
+//! for tracking figures like 95th-percentile loop latency on-device without storing
+//! samples. Five markers follow the minimum, the target quantile, the maximum, and the
+//! points halfway between; the estimate is the middle marker.
+//!
+//! ```
+//! use bare_metal_deque::P2Quantile;
+//!
+//! let mut p95 = P2Quantile::new(0.95);
+//! for i in 0..1000 {
+//!     p95.push(((i * 7919) % 1000) as f32);
+//! }
+//! let estimate = p95.estimate().unwrap();
+//! assert!((estimate - 950.0).abs() < 10.0);
+//! ```
+
+#[derive(Copy, Clone, Debug)]
+pub struct P2Quantile {
+    /// Marker heights.
+    heights: [f32; 5],
+    /// Marker positions, counted from 1.
+    positions: [f32; 5],
+    desired: [f32; 5],
+    increments: [f32; 5],
+    count: u32,
+}
+
+impl P2Quantile {
+    /// Creates an estimator for quantile `p`, e.g. 0.95 for the 95th percentile. Panics
+    /// unless `0 < p < 1`.
+    pub fn new(p: f32) -> Self {
+        if !(p > 0.0 && p < 1.0) {
+            panic!("Quantile must be between 0 and 1");
+        }
+        Self {
+            heights: [0.0; 5],
+            positions: [1.0, 2.0, 3.0, 4.0, 5.0],
+            desired: [1.0, 1.0 + 2.0 * p, 1.0 + 4.0 * p, 3.0 + 2.0 * p, 5.0],
+            increments: [0.0, p / 2.0, p, (1.0 + p) / 2.0, 1.0],
+            count: 0,
+        }
+    }
+
+    /// Number of samples seen.
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    /// Adds a sample. NaN samples are ignored.
+    pub fn push(&mut self, x: f32) {
+        if x.is_nan() {
+            return;
+        }
+        if self.count < 5 {
+            self.heights[self.count as usize] = x;
+            self.count += 1;
+            if self.count == 5 {
+                self.heights.sort_unstable_by(f32::total_cmp);
+            }
+            return;
+        }
+        self.count = self.count.saturating_add(1);
+
+        let h = &mut self.heights;
+        let cell = if x < h[0] {
+            h[0] = x;
+            0
+        } else if x >= h[4] {
+            h[4] = x;
+            3
+        } else {
+            (1..5).find(|i| x < h[*i]).unwrap() - 1
+        };
+        for position in &mut self.positions[cell + 1..] {
+            *position += 1.0;
+        }
+        for (desired, increment) in self.desired.iter_mut().zip(self.increments) {
+            *desired += increment;
+        }
+
+        for i in 1..4 {
+            let offset = self.desired[i] - self.positions[i];
+            let room_above = self.positions[i + 1] - self.positions[i] > 1.0;
+            let room_below = self.positions[i - 1] - self.positions[i] < -1.0;
+            if (offset >= 1.0 && room_above) || (offset <= -1.0 && room_below) {
+                let d = if offset > 0.0 { 1.0 } else { -1.0 };
+                let candidate = self.parabolic(i, d);
+                self.heights[i] = if self.heights[i - 1] < candidate && candidate < self.heights[i + 1] {
+                    candidate
+                } else {
+                    self.linear(i, d)
+                };
+                self.positions[i] += d;
+            }
+        }
+    }
+
+    /// The current estimate, or `None` before any samples. With fewer than five samples,
+    /// this is the nearest-rank quantile of those seen.
+    pub fn estimate(&self) -> Option<f32> {
+        match self.count {
+            0 => None,
+            n @ 1..5 => {
+                let mut seen = [0.0; 5];
+                let seen = &mut seen[..n as usize];
+                seen.copy_from_slice(&self.heights[..n as usize]);
+                seen.sort_unstable_by(f32::total_cmp);
+                let rank = ((self.increments[2] * n as f32) as usize).min(n as usize - 1);
+                Some(seen[rank])
+            }
+            _ => Some(self.heights[2]),
+        }
+    }
+
+    fn parabolic(&self, i: usize, d: f32) -> f32 {
+        let (q, n) = (&self.heights, &self.positions);
+        q[i] + d / (n[i + 1] - n[i - 1])
+            * ((n[i] - n[i - 1] + d) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                + (n[i + 1] - n[i] - d) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]))
+    }
+
+    fn linear(&self, i: usize, d: f32) -> f32 {
+        let j = if d > 0.0 { i + 1 } else { i - 1 };
+        self.heights[i] + d * (self.heights[j] - self.heights[i]) / (self.positions[j] - self.positions[i])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn median_test() {
+        let mut median = P2Quantile::new(0.5);
+        assert_eq!(median.estimate(), None);
+        median.push(3.0);
+        median.push(1.0);
+        median.push(2.0);
+        median.push(f32::NAN);
+        assert_eq!(median.estimate(), Some(2.0));
+
+        for i in 0..10_000u32 {
+            median.push((i.wrapping_mul(2_654_435_761) % 1000) as f32);
+        }
+        let estimate = median.estimate().unwrap();
+        assert!((estimate - 500.0).abs() < 15.0, "{estimate}");
+        assert_eq!(median.count(), 10_003);
+    }
+}
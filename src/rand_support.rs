@@ -0,0 +1,117 @@
+//! Random permutation and sampling of deque contents, behind the `rand_core` feature.
+//!
+//! Any [`RngCore`] works, including hardware RNG peripherals and small seeded PRNGs.
+//!
+//! ```
+//! use bare_metal_deque::BareMetalDeque;
+//! use rand_core::{RngCore, impls};
+//!
+//! struct Xorshift(u32);
+//!
+//! impl RngCore for Xorshift {
+//!     fn next_u32(&mut self) -> u32 {
+//!         self.0 ^= self.0 << 13;
+//!         self.0 ^= self.0 >> 17;
+//!         self.0 ^= self.0 << 5;
+//!         self.0
+//!     }
+//!     fn next_u64(&mut self) -> u64 {
+//!         impls::next_u64_via_u32(self)
+//!     }
+//!     fn fill_bytes(&mut self, dst: &mut [u8]) {
+//!         impls::fill_bytes_via_next(self, dst)
+//!     }
+//! }
+//!
+//! let mut rng = Xorshift(0x1234_5678);
+//! let mut deck = BareMetalDeque::<u8, 8>::new();
+//! for card in 1..=8 {
+//!     deck.push_back(card);
+//! }
+//! deck.shuffle(&mut rng);
+//!
+//! let mut sorted = deck.iter().copied().collect::<Vec<_>>();
+//! sorted.sort();
+//! assert_eq!(sorted, (1..=8).collect::<Vec<_>>());
+//! assert!(deck.choose(&mut rng).is_some());
+//! ```
+
+use rand_core::RngCore;
+
+use crate::BareMetalDeque;
+
+impl<T: Copy + Clone + Default, const MAX_STORED: usize> BareMetalDeque<T, MAX_STORED> {
+    /// Randomly permutes the contents in place, with every ordering equally likely.
+    pub fn shuffle<R: RngCore + ?Sized>(&mut self, rng: &mut R) {
+        for i in (1..self.len()).rev() {
+            let j = below(rng, i + 1);
+            let tmp = self[i];
+            self[i] = self[j];
+            self[j] = tmp;
+        }
+    }
+
+    /// Returns a uniformly chosen element, or `None` if the deque is empty.
+    pub fn choose<R: RngCore + ?Sized>(&self, rng: &mut R) -> Option<T> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self[below(rng, self.len())])
+        }
+    }
+}
+
+/// Uniform value in `0..n`, rejecting the low values that would bias the modulo.
+fn below<R: RngCore + ?Sized>(rng: &mut R, n: usize) -> usize {
+    let n = n as u64;
+    let threshold = n.wrapping_neg() % n;
+    loop {
+        let x = rng.next_u64();
+        if x >= threshold {
+            return (x % n) as usize;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::impls;
+
+    /// Replays a fixed sequence of outputs.
+    struct Script<'a>(&'a [u64]);
+
+    impl RngCore for Script<'_> {
+        fn next_u32(&mut self) -> u32 {
+            self.next_u64() as u32
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            let (first, rest) = self.0.split_first().unwrap();
+            self.0 = rest;
+            *first
+        }
+
+        fn fill_bytes(&mut self, dst: &mut [u8]) {
+            impls::fill_bytes_via_next(self, dst)
+        }
+    }
+
+    #[test]
+    fn shuffle_choose_test() {
+        let mut q = BareMetalDeque::<u8, 4>::new();
+        assert_eq!(q.choose(&mut Script(&[])), None);
+        for x in [9, 1, 2, 3] {
+            q.push_back(x);
+        }
+        q.pop_front();
+        q.push_back(4);
+
+        // Swaps index 3 with 0, then 2 with 2, then 1 with 0.
+        q.shuffle(&mut Script(&[4, 5, 2]));
+        assert_eq!(q.iter().copied().collect::<Vec<_>>(), vec![2, 4, 3, 1]);
+        // With three elements, a draw of 0 falls in the biased zone and is rejected.
+        q.pop_back();
+        assert_eq!(q.choose(&mut Script(&[0, 7])), Some(4));
+    }
+}
@@ -0,0 +1,118 @@
+//! A queue of variable-length records of mixed types sharing one byte ring, so several
+//! message types do not each need their own fixed-slot queue. Each record is stored as a
+//! one-byte tag identifying its type, a two-byte little-endian payload length, and the
+//! payload. A visitor given the tag and payload decodes each record on the way out.
+//!
+//! ```
+//! use bare_metal_deque::RecordQueue;
+//!
+//! const TEMPERATURE: u8 = 1;
+//! const LOG: u8 = 2;
+//!
+//! let mut q = RecordQueue::<64>::new();
+//! q.push_record(TEMPERATURE, &(-40i16).to_le_bytes()).unwrap();
+//! q.push_record(LOG, b"fan on").unwrap();
+//!
+//! let describe = |tag, bytes: &[u8]| match tag {
+//!     TEMPERATURE => format!("{} C", i16::from_le_bytes(bytes.try_into().unwrap())),
+//!     LOG => String::from_utf8_lossy(bytes).into_owned(),
+//!     _ => String::from("?"),
+//! };
+//! assert_eq!(q.pop_record(describe).unwrap(), "-40 C");
+//! assert_eq!(q.pop_record(describe).unwrap(), "fan on");
+//! assert!(q.pop_record(describe).is_none());
+//! ```
+
+use crate::{ByteDeque, FrameError};
+
+const HEADER_LEN: usize = 3;
+
+#[derive(Copy, Clone, Debug)]
+pub struct RecordQueue<const N: usize> {
+    bytes: ByteDeque<N>,
+    records: usize,
+}
+
+impl<const N: usize> Default for RecordQueue<N> {
+    fn default() -> Self {
+        Self { bytes: ByteDeque::new(), records: 0 }
+    }
+}
+
+impl<const N: usize> RecordQueue<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of queued records.
+    pub fn len(&self) -> usize {
+        self.records
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records == 0
+    }
+
+    /// Number of backing bytes in use, headers included.
+    pub fn bytes_used(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Appends a record of type `tag` holding `payload`. Nothing is written on failure.
+    pub fn push_record(&mut self, tag: u8, payload: &[u8]) -> Result<(), FrameError> {
+        let len = u16::try_from(payload.len()).map_err(|_| FrameError::TooLong)?;
+        if payload.len() + HEADER_LEN > N - self.bytes.len() {
+            return Err(FrameError::NoSpace);
+        }
+        let [low, high] = len.to_le_bytes();
+        self.bytes.write(&[tag, low, high]);
+        self.bytes.write(payload);
+        self.records += 1;
+        Ok(())
+    }
+
+    /// Tag of the oldest record, if any.
+    pub fn peek_tag(&self) -> Option<u8> {
+        (self.records > 0).then(|| self.bytes[0])
+    }
+
+    /// Removes the oldest record and returns what `visit` makes of its tag and payload.
+    /// If the payload wraps around the end of the ring, the ring is first rotated so the
+    /// payload can be passed as one slice.
+    pub fn pop_record<R, F: FnOnce(u8, &[u8]) -> R>(&mut self, visit: F) -> Option<R> {
+        let tag = self.peek_tag()?;
+        let len = u16::from_le_bytes([self.bytes[1], self.bytes[2]]) as usize;
+        self.bytes.discard(HEADER_LEN);
+        if self.bytes.as_slices().0.len() < len {
+            self.bytes.make_contiguous();
+        }
+        let result = visit(tag, &self.bytes.as_slices().0[..len]);
+        self.bytes.discard(len);
+        self.records -= 1;
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapped_record_test() {
+        let mut q = RecordQueue::<10>::new();
+        q.push_record(1, b"ab").unwrap();
+        assert_eq!(q.push_record(2, b"abcdef"), Err(FrameError::NoSpace));
+        assert_eq!(q.pop_record(|tag, bytes| (tag, bytes.len())), Some((1, 2)));
+        q.push_record(2, b"wxyz").unwrap();
+        q.push_record(3, b"").unwrap();
+        assert_eq!((q.len(), q.bytes_used(), q.peek_tag()), (2, 10, Some(2)));
+        assert_eq!(q.pop_record(|tag, bytes| (tag, bytes.to_vec())), Some((2, b"wxyz".to_vec())));
+        assert_eq!(q.pop_record(|tag, bytes| (tag, bytes.to_vec())), Some((3, vec![])));
+        assert!(q.is_empty());
+        assert_eq!(q.push_record(0, &[0; 70_000]), Err(FrameError::TooLong));
+    }
+}
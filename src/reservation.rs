@@ -0,0 +1,97 @@
+//! Reserving space for a multi-part message, so it is either enqueued completely or not
+//! at all.
+//!
+//! Elements pushed through a [`Reservation`] become part of the deque immediately, but
+//! dropping the reservation without calling [`Reservation::commit`] removes them again,
+//! so an error path part way through a message cannot leave a partial frame queued.
+//!
+//! ```
+//! use bare_metal_deque::BareMetalDeque;
+//!
+//! let mut tx = BareMetalDeque::<u8, 8>::new();
+//! let mut frame = tx.reserve(3).unwrap();
+//! frame.push(0x7E).unwrap();
+//! frame.push(0x01).unwrap();
+//! drop(frame); // abandoned before the checksum byte
+//! assert!(tx.is_empty());
+//!
+//! let mut frame = tx.reserve(3).unwrap();
+//! for b in [0x7E, 0x01, 0x7F] {
+//!     frame.push(b).unwrap();
+//! }
+//! frame.commit();
+//! assert_eq!(tx.len(), 3);
+//! assert!(tx.reserve(6).is_none());
+//! ```
+
+use crate::{BareMetalDeque, CapacityError};
+
+#[derive(Debug)]
+pub struct Reservation<'a, T: Copy + Default, const MAX_STORED: usize> {
+    deque: &'a mut BareMetalDeque<T, MAX_STORED>,
+    remaining: usize,
+    pushed: usize,
+}
+
+impl<T: Copy + Default, const MAX_STORED: usize> BareMetalDeque<T, MAX_STORED> {
+    /// Reserves space for `n` pushes to the back, or returns `None` if fewer than `n`
+    /// slots are free.
+    pub fn reserve(&mut self, n: usize) -> Option<Reservation<'_, T, MAX_STORED>> {
+        (MAX_STORED - self.len() >= n).then_some(Reservation { deque: self, remaining: n, pushed: 0 })
+    }
+}
+
+impl<T: Copy + Default, const MAX_STORED: usize> Reservation<'_, T, MAX_STORED> {
+    /// Number of pushes still reserved.
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Appends `value` to the deque, failing only if the reserved pushes are used up.
+    pub fn push(&mut self, value: T) -> Result<(), CapacityError> {
+        if self.remaining == 0 {
+            return Err(CapacityError);
+        }
+        self.deque.try_push_back(value)?;
+        self.remaining -= 1;
+        self.pushed += 1;
+        Ok(())
+    }
+
+    /// Keeps the pushed elements in the deque.
+    pub fn commit(mut self) {
+        self.pushed = 0;
+    }
+}
+
+impl<T: Copy + Default, const MAX_STORED: usize> Drop for Reservation<'_, T, MAX_STORED> {
+    fn drop(&mut self) {
+        self.deque.size -= self.pushed;
+        self.deque.record_pops(self.pushed);
+        self.deque.debug_check_invariants();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rollback_test() {
+        let mut q = BareMetalDeque::<u8, 4>::new();
+        q.extend_from_slice(&[1, 2, 3]);
+        q.pop_front();
+        let mut r = q.reserve(2).unwrap();
+        assert_eq!(r.push(4), Ok(()));
+        assert_eq!(r.remaining(), 1);
+        assert_eq!(r.push(5), Ok(()));
+        assert_eq!(r.push(6), Err(CapacityError));
+        drop(r);
+        assert_eq!(q.view(..), [2, 3]);
+
+        let mut r = q.reserve(1).unwrap();
+        r.push(4).unwrap();
+        r.commit();
+        assert_eq!(q.view(..), [2, 3, 4]);
+    }
+}
@@ -0,0 +1,162 @@
+//! A [`log`](https://docs.rs/log) backend that formats records into a byte ring, for the
+//! main loop to flush to a UART or other slow output at its convenience. When the ring
+//! fills up, the oldest complete lines are overwritten. Requires the `log` feature, and a
+//! [`critical-section`](https://docs.rs/critical-section) implementation for the target.
+//!
+//! ```
+//! use bare_metal_deque::RingLogger;
+//! use log::LevelFilter;
+//!
+//! static LOGGER: RingLogger<256> = RingLogger::new(LevelFilter::Info);
+//!
+//! log::set_logger(&LOGGER).unwrap();
+//! log::set_max_level(LevelFilter::Info);
+//! log::info!(target: "sensor", "temperature {}", 21);
+//! log::debug!("not recorded");
+//!
+//! let mut out = Vec::new();
+//! LOGGER.drain(|bytes| out.extend_from_slice(bytes));
+//! assert_eq!(out, b"INFO sensor: temperature 21\n");
+//! ```
+
+use core::cell::RefCell;
+use core::fmt::{self, Write};
+
+use critical_section::Mutex;
+use log::{LevelFilter, Log, Metadata, Record};
+
+use crate::ByteDeque;
+
+const DRAIN_CHUNK: usize = 32;
+
+pub struct RingLogger<const N: usize> {
+    ring: Mutex<RefCell<ByteDeque<N>>>,
+    level: LevelFilter,
+}
+
+impl<const N: usize> RingLogger<N> {
+    pub const fn new(level: LevelFilter) -> Self {
+        Self { ring: Mutex::new(RefCell::new(ByteDeque::new_with(0))), level }
+    }
+
+    /// Number of buffered bytes awaiting `drain()`.
+    pub fn len(&self) -> usize {
+        critical_section::with(|cs| self.ring.borrow_ref(cs).len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Passes the buffered bytes to `output` in order, removing them from the ring. Bytes
+    /// are copied out in small chunks, so `output` runs outside the critical section and
+    /// may itself log; anything logged while draining is left for the next call.
+    pub fn drain<F: FnMut(&[u8])>(&self, mut output: F) {
+        let mut remaining = self.len();
+        while remaining > 0 {
+            let mut chunk = [0; DRAIN_CHUNK];
+            let limit = remaining.min(DRAIN_CHUNK);
+            let n = critical_section::with(|cs| self.ring.borrow_ref_mut(cs).read(&mut chunk[..limit]));
+            if n == 0 {
+                break;
+            }
+            output(&chunk[..n]);
+            remaining -= n;
+        }
+    }
+}
+
+impl<const N: usize> Log for RingLogger<N> {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        critical_section::with(|cs| {
+            let mut ring = self.ring.borrow_ref_mut(cs);
+            let mut line = LineWriter { ring: &mut ring, line_len: 0, evicted: false };
+            let _ = writeln!(line, "{} {}: {}", record.level(), record.target(), record.args());
+            line.finish();
+        });
+    }
+
+    fn flush(&self) {}
+}
+
+/// Appends one line to the ring, evicting older bytes to make room and truncating the
+/// line if it is longer than the ring itself.
+struct LineWriter<'a, const N: usize> {
+    ring: &'a mut ByteDeque<N>,
+    line_len: usize,
+    evicted: bool,
+}
+
+impl<const N: usize> LineWriter<'_, N> {
+    fn finish(self) {
+        if self.line_len == 0 {
+            return;
+        }
+        let last = self.ring.len() - 1;
+        self.ring[last] = b'\n';
+        if self.evicted {
+            // Don't leave the tail of a partially overwritten line at the front.
+            let older = self.ring.len() - self.line_len;
+            let partial = self.ring.iter().take(older).position(|b| *b == b'\n').map_or(older, |i| i + 1);
+            self.ring.discard(partial);
+        }
+    }
+}
+
+impl<const N: usize> Write for LineWriter<'_, N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for b in s.bytes() {
+            if self.ring.is_full() {
+                if self.ring.len() == self.line_len {
+                    return Ok(());
+                }
+                self.ring.pop_front();
+                self.evicted = true;
+            }
+            self.ring.push_back(b);
+            self.line_len += 1;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use log::Level;
+
+    fn log_line<const N: usize>(logger: &RingLogger<N>, level: Level, message: &str) {
+        logger.log(&Record::builder().level(level).target("t").args(format_args!("{}", message)).build());
+    }
+
+    fn drained<const N: usize>(logger: &RingLogger<N>) -> String {
+        let mut out = Vec::new();
+        logger.drain(|bytes| out.extend_from_slice(bytes));
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn overwrite_test() {
+        let logger = RingLogger::<32>::new(LevelFilter::Info);
+        log_line(&logger, Level::Info, "one");
+        log_line(&logger, Level::Trace, "hidden");
+        log_line(&logger, Level::Warn, "two");
+        assert_eq!(drained(&logger), "INFO t: one\nWARN t: two\n");
+        assert!(logger.is_empty());
+
+        log_line(&logger, Level::Info, "one");
+        log_line(&logger, Level::Warn, "two");
+        log_line(&logger, Level::Error, "three");
+        assert_eq!(drained(&logger), "WARN t: two\nERROR t: three\n");
+
+        log_line(&logger, Level::Error, "a message that is much too long");
+        assert_eq!(drained(&logger), "ERROR t: a message that is much\n");
+    }
+}
@@ -0,0 +1,112 @@
+//! A ring of the most recent image rows, for streaming 3×3 or 5×5 convolutions over
+//! camera data with only a few lines of RAM rather than a whole frame.
+//!
+//! ```
+//! use bare_metal_deque::ScanlineRing;
+//!
+//! let mut lines = ScanlineRing::<u8, 4, 3>::new();
+//! lines.push_row(&[1, 2, 3, 4]);
+//! lines.push_row(&[5, 6, 7, 8]);
+//! assert_eq!(lines.window::<3>(0), None); // not enough rows yet
+//! lines.push_row(&[9, 10, 11, 12]);
+//!
+//! // Sum of the 3×3 neighborhood around column 2 of the middle row.
+//! let block = lines.window::<3>(1).unwrap();
+//! assert_eq!(block.iter().flatten().map(|&x| u32::from(x)).sum::<u32>(), 63);
+//! ```
+
+#[derive(Copy, Clone, Debug)]
+pub struct ScanlineRing<T, const W: usize, const LINES: usize> {
+    rows: [[T; W]; LINES],
+    /// Index in `rows` of the oldest row held.
+    oldest: usize,
+    len: usize,
+}
+
+impl<T: Copy + Default, const W: usize, const LINES: usize> Default for ScanlineRing<T, W, LINES> {
+    fn default() -> Self {
+        Self { rows: [[T::default(); W]; LINES], oldest: 0, len: 0 }
+    }
+}
+
+impl<T: Copy + Default, const W: usize, const LINES: usize> ScanlineRing<T, W, LINES> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of rows held, up to `LINES`.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes all rows, as at the start of a new frame.
+    pub fn clear(&mut self) {
+        self.oldest = 0;
+        self.len = 0;
+    }
+
+    /// Appends `row` as the newest row, dropping the oldest if `LINES` rows are held.
+    pub fn push_row(&mut self, row: &[T; W]) {
+        self.push_row_with(|dst| *dst = *row);
+    }
+
+    /// Appends a new row filled in place by `fill`, as directly from a camera line
+    /// buffer. The slot passed to `fill` holds stale data.
+    pub fn push_row_with<F: FnOnce(&mut [T; W])>(&mut self, fill: F) {
+        if LINES == 0 {
+            return;
+        }
+        let slot = (self.oldest + self.len) % LINES;
+        fill(&mut self.rows[slot]);
+        if self.len == LINES {
+            self.oldest = (self.oldest + 1) % LINES;
+        } else {
+            self.len += 1;
+        }
+    }
+
+    /// Row `index` of those held, where 0 is the oldest.
+    pub fn row(&self, index: usize) -> Option<&[T; W]> {
+        (index < self.len).then(|| &self.rows[(self.oldest + index) % LINES])
+    }
+
+    /// The `K`×`K` block of the newest `K` rows starting at column `col`, oldest row
+    /// first. Returns `None` if fewer than `K` rows are held or the block would extend
+    /// past the right edge.
+    pub fn window<const K: usize>(&self, col: usize) -> Option<[[T; K]; K]> {
+        if K > self.len || col.checked_add(K)? > W {
+            return None;
+        }
+        let first = self.len - K;
+        Some(core::array::from_fn(|r| {
+            let row = &self.rows[(self.oldest + first + r) % LINES];
+            core::array::from_fn(|c| row[col + c])
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rolling_rows_test() {
+        let mut lines = ScanlineRing::<i16, 3, 2>::new();
+        for r in 0..5 {
+            lines.push_row_with(|dst| *dst = [r, r * 10, r * 100]);
+        }
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines.row(0), Some(&[3, 30, 300]));
+        assert_eq!(lines.row(2), None);
+        assert_eq!(lines.window::<2>(1), Some([[30, 300], [40, 400]]));
+        assert_eq!(lines.window::<2>(2), None);
+        assert_eq!(lines.window::<1>(0), Some([[4]]));
+        lines.clear();
+        assert!(lines.is_empty());
+        ScanlineRing::<i16, 3, 0>::new().push_row(&[1, 2, 3]);
+    }
+}
@@ -0,0 +1,85 @@
+//! `serde` support, behind the `serde` feature. A deque serializes as a sequence of its
+//! logical contents, front first. Deserializing a sequence longer than the deque's
+//! capacity fails with an `invalid_length` error rather than panicking.
+//!
+//! ```
+//! use bare_metal_deque::BareMetalDeque;
+//!
+//! let mut q = BareMetalDeque::<u8, 4>::new();
+//! q.push_back(1);
+//! q.push_front(0);
+//! assert_eq!(serde_json::to_string(&q).unwrap(), "[0,1]");
+//!
+//! let q: BareMetalDeque<u8, 4> = serde_json::from_str("[5,6,7]").unwrap();
+//! assert_eq!(q.len(), 3);
+//! assert!(serde_json::from_str::<BareMetalDeque<u8, 2>>("[5,6,7]").is_err());
+//! ```
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+
+use crate::BareMetalDeque;
+
+impl<T: Copy + Default + Serialize, const MAX_STORED: usize> Serialize for BareMetalDeque<T, MAX_STORED> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for value in self.iter() {
+            seq.serialize_element(value)?;
+        }
+        seq.end()
+    }
+}
+
+impl<'de, T: Copy + Default + Deserialize<'de>, const MAX_STORED: usize> Deserialize<'de> for BareMetalDeque<T, MAX_STORED> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(DequeVisitor(PhantomData))
+    }
+}
+
+struct DequeVisitor<T, const MAX_STORED: usize>(PhantomData<T>);
+
+impl<'de, T: Copy + Default + Deserialize<'de>, const MAX_STORED: usize> Visitor<'de> for DequeVisitor<T, MAX_STORED> {
+    type Value = BareMetalDeque<T, MAX_STORED>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a sequence of at most {} elements", MAX_STORED)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut result = BareMetalDeque::new();
+        while let Some(value) = seq.next_element()? {
+            if result.is_full() {
+                return Err(A::Error::invalid_length(MAX_STORED + 1, &self));
+            }
+            result.push_back(value);
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BareMetalDeque;
+
+    #[test]
+    fn round_trip_test() {
+        let mut q = BareMetalDeque::<i32, 3>::new();
+        for x in [1, 2, 3] {
+            q.push_back(x);
+        }
+        q.pop_front();
+        q.push_back(4);
+        let json = serde_json::to_string(&q).unwrap();
+        assert_eq!(json, "[2,3,4]");
+
+        let restored: BareMetalDeque<i32, 3> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+
+        let err = serde_json::from_str::<BareMetalDeque<i32, 2>>(&json).unwrap_err();
+        assert!(err.to_string().contains("at most 2 elements"));
+        assert!(serde_json::from_str::<BareMetalDeque<i32, 2>>("{}").is_err());
+    }
+}
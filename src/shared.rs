@@ -0,0 +1,51 @@
+//! Interrupt-safe global deques, behind the `critical-section` feature.
+//!
+//! ```
+//! use bare_metal_deque::static_deque;
+//!
+//! static_deque!(pub RX: u8, 64, 0);
+//!
+//! // In the UART interrupt handler:
+//! critical_section::with(|cs| RX.borrow_ref_mut(cs).push_back(b'x'));
+//!
+//! // In the main loop:
+//! let byte = critical_section::with(|cs| RX.borrow_ref_mut(cs).pop_front());
+//! assert_eq!(byte, Some(b'x'));
+//! ```
+
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+
+use crate::BareMetalDeque;
+
+/// A deque that can live in a `static` and be shared with interrupt handlers. Access it
+/// inside `critical_section::with`, as in `Q.borrow_ref_mut(cs).push_back(x)`.
+pub type SharedDeque<T, const MAX_STORED: usize> = Mutex<RefCell<BareMetalDeque<T, MAX_STORED>>>;
+
+/// Declares a `static` [`SharedDeque`] holding elements of type `$t`, with capacity
+/// `$capacity`. Unused storage is filled with `$fill`, which must be a constant.
+#[macro_export]
+macro_rules! static_deque {
+    ($vis:vis $name:ident: $t:ty, $capacity:expr, $fill:expr) => {
+        $vis static $name: $crate::SharedDeque<$t, $capacity> =
+            <$crate::SharedDeque<$t, $capacity>>::new(::core::cell::RefCell::new($crate::BareMetalDeque::new_with($fill)));
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    static_deque!(EVENTS: (u8, i32), 2, (0, 0));
+
+    #[test]
+    fn static_deque_test() {
+        critical_section::with(|cs| {
+            let mut events = EVENTS.borrow_ref_mut(cs);
+            events.push_back((1, -5));
+            events.push_back((2, 7));
+            assert!(events.is_full());
+        });
+        let first = critical_section::with(|cs| EVENTS.borrow_ref_mut(cs).pop_front());
+        assert_eq!(first, Some((1, -5)));
+    }
+}
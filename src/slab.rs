@@ -0,0 +1,175 @@
+//! A fixed-capacity slab: values stored in numbered slots, so connection or session state
+//! can be referred to by small keys that stay valid until the value is removed, and
+//! queues can carry the keys instead of the state itself. Free slots are kept on a list,
+//! so inserting and removing take constant time.
+//!
+//! A key records its slot's generation, which advances whenever the slot is freed, so a
+//! stale key left in a queue finds nothing rather than a newer value in the same slot.
+//!
+//! ```
+//! use bare_metal_deque::{BareMetalDeque, BareMetalSlab, SlabKey};
+//!
+//! let mut sessions = BareMetalSlab::<(u32, u16), 8>::new();
+//! let mut ready = BareMetalDeque::<SlabKey, 8>::new();
+//!
+//! let key = sessions.insert((0x0A00_0001, 443)).unwrap();
+//! ready.push_back(key);
+//!
+//! let key = ready.pop_front().unwrap();
+//! assert_eq!(sessions.get(key), Some(&(0x0A00_0001, 443)));
+//! assert_eq!(sessions.remove(key), Some((0x0A00_0001, 443)));
+//! assert_eq!(sessions.get(key), None);
+//! ```
+
+use crate::CapacityError;
+
+/// A handle to a value in a [`BareMetalSlab`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SlabKey {
+    index: usize,
+    generation: u32,
+}
+
+impl SlabKey {
+    /// The slot the value occupies, which is reused after the value is removed.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct BareMetalSlab<T, const N: usize> {
+    slots: [Option<T>; N],
+    generations: [u32; N],
+    /// For each free slot, the next free slot, or `N` at the end of the list.
+    next_free: [usize; N],
+    free_head: usize,
+    len: usize,
+}
+
+impl<T: Copy, const N: usize> Default for BareMetalSlab<T, N> {
+    fn default() -> Self {
+        Self { slots: [None; N], generations: [0; N], next_free: core::array::from_fn(|i| i + 1), free_head: 0, len: 0 }
+    }
+}
+
+impl<T: Copy, const N: usize> BareMetalSlab<T, N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Stores `value` in a free slot and returns its key, or fails if every slot is used.
+    pub fn insert(&mut self, value: T) -> Result<SlabKey, CapacityError> {
+        let index = self.free_head;
+        if index == N {
+            return Err(CapacityError);
+        }
+        self.free_head = self.next_free[index];
+        self.slots[index] = Some(value);
+        self.len += 1;
+        Ok(SlabKey { index, generation: self.generations[index] })
+    }
+
+    pub fn get(&self, key: SlabKey) -> Option<&T> {
+        if !self.is_current(key) {
+            return None;
+        }
+        self.slots[key.index].as_ref()
+    }
+
+    pub fn get_mut(&mut self, key: SlabKey) -> Option<&mut T> {
+        if !self.is_current(key) {
+            return None;
+        }
+        self.slots[key.index].as_mut()
+    }
+
+    pub fn contains(&self, key: SlabKey) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Removes and returns the value stored under `key`, freeing its slot.
+    pub fn remove(&mut self, key: SlabKey) -> Option<T> {
+        if !self.is_current(key) {
+            return None;
+        }
+        let value = self.slots[key.index].take()?;
+        self.generations[key.index] = self.generations[key.index].wrapping_add(1);
+        self.next_free[key.index] = self.free_head;
+        self.free_head = key.index;
+        self.len -= 1;
+        Some(value)
+    }
+
+    /// Iterates over the stored values with their keys, in slot order.
+    pub fn iter(&self) -> impl Iterator<Item = (SlabKey, &T)> {
+        self.slots.iter().zip(self.generations).enumerate().filter_map(|(index, (slot, generation))| {
+            Some((SlabKey { index, generation }, slot.as_ref()?))
+        })
+    }
+
+    /// Removes every value. Keys from before the call stay invalid.
+    pub fn clear(&mut self) {
+        for index in 0..N {
+            if self.slots[index].is_some() {
+                self.remove(SlabKey { index, generation: self.generations[index] });
+            }
+        }
+    }
+
+    fn is_current(&self, key: SlabKey) -> bool {
+        self.generations.get(key.index) == Some(&key.generation)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn free_list_test() {
+        let mut slab = BareMetalSlab::<char, 3>::new();
+        let keys: Vec<_> = "abc".chars().map(|c| slab.insert(c).unwrap()).collect();
+        assert_eq!(keys.iter().map(SlabKey::index).collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(slab.insert('d'), Err(CapacityError));
+        assert_eq!(slab.remove(keys[1]), Some('b'));
+        assert_eq!(slab.remove(keys[1]), None);
+        assert_eq!(slab.remove(keys[0]), Some('a'));
+        let e = slab.insert('e').unwrap();
+        let f = slab.insert('f').unwrap();
+        assert_eq!((e.index(), f.index()), (0, 1));
+        *slab.get_mut(keys[2]).unwrap() = 'C';
+        assert_eq!(slab.iter().map(|(_, c)| *c).collect::<String>(), "efC");
+        assert!(slab.is_full());
+        assert!(BareMetalSlab::<u8, 0>::new().insert(1).is_err());
+    }
+
+    #[test]
+    fn stale_key_test() {
+        let mut slab = BareMetalSlab::<u32, 2>::new();
+        let old = slab.insert(1).unwrap();
+        slab.remove(old);
+        let new = slab.insert(2).unwrap();
+        assert_eq!(old.index(), new.index());
+        assert_eq!(slab.get(old), None);
+        assert_eq!(slab.get_mut(old), None);
+        assert_eq!(slab.remove(old), None);
+        assert_eq!(slab.get(new), Some(&2));
+
+        slab.clear();
+        assert_eq!(slab.get(new), None);
+        assert_eq!(slab.insert(3).map(|key| slab.get(key).copied()), Ok(Some(3)));
+    }
+}
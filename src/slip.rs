@@ -0,0 +1,157 @@
+//! [SLIP](https://datatracker.ietf.org/doc/html/rfc1055) framing over byte deques.
+//! As RFC 1055 suggests, each encoded frame starts and ends with an `END` byte, so
+//! line noise received before a frame is discarded as an empty or corrupt frame
+//! rather than being glued onto the next one.
+//!
+//! ```
+//! use bare_metal_deque::{ByteDeque, slip};
+//!
+//! let mut link = ByteDeque::<32>::new();
+//! slip::encode_into(&[0x01, 0xC0, 0xDB], &mut link).unwrap();
+//!
+//! let mut frame = [0; 8];
+//! assert_eq!(slip::decode_from(&mut link, &mut frame), Some(Ok(3)));
+//! assert_eq!(&frame[..3], &[0x01, 0xC0, 0xDB]);
+//! assert_eq!(slip::decode_from(&mut link, &mut frame), None);
+//! ```
+
+use crate::ByteDeque;
+
+pub const END: u8 = 0xC0;
+pub const ESC: u8 = 0xDB;
+pub const ESC_END: u8 = 0xDC;
+pub const ESC_ESC: u8 = 0xDD;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SlipError {
+    /// The destination deque cannot hold the whole encoded frame.
+    NoSpace,
+    /// The decoded frame does not fit in the destination buffer.
+    TooLong,
+    /// The frame contains an invalid escape sequence.
+    Corrupt,
+    /// The receive deque filled up without a delimiter, so its contents were discarded.
+    Overrun,
+}
+
+/// Number of bytes `frame` occupies once encoded, including both `END` bytes.
+pub fn encoded_len(frame: &[u8]) -> usize {
+    2 + frame.iter().map(|b| if *b == END || *b == ESC { 2 } else { 1 }).sum::<usize>()
+}
+
+/// Appends the encoded `frame` to `tx`, returning the number of bytes written.
+/// Nothing is written if the whole frame does not fit.
+pub fn encode_into<const N: usize>(frame: &[u8], tx: &mut ByteDeque<N>) -> Result<usize, SlipError> {
+    let len = encoded_len(frame);
+    if len > N - tx.len() {
+        return Err(SlipError::NoSpace);
+    }
+    tx.push_back(END);
+    for b in frame {
+        match *b {
+            END => tx.write(&[ESC, ESC_END]),
+            ESC => tx.write(&[ESC, ESC_ESC]),
+            b => tx.write(&[b]),
+        };
+    }
+    tx.push_back(END);
+    Ok(len)
+}
+
+/// Removes the next complete frame from `rx` and decodes it into `dst`, returning the
+/// decoded length. Returns `None` if no complete frame is buffered. A frame that is
+/// corrupt or too long for `dst` is still removed, so decoding resynchronizes at the
+/// next `END`. If `rx` fills up without an `END`, its contents are discarded so that
+/// decoding can resynchronize.
+pub fn decode_from<const N: usize>(rx: &mut ByteDeque<N>, dst: &mut [u8]) -> Option<Result<usize, SlipError>> {
+    loop {
+        let Some(end) = rx.find_byte(END) else {
+            if rx.is_full() {
+                rx.discard(rx.len());
+                return Some(Err(SlipError::Overrun));
+            }
+            return None;
+        };
+        if end == 0 {
+            rx.pop_front();
+            continue;
+        }
+        let result = decode(rx, end, dst);
+        rx.discard(end + 1);
+        return Some(result);
+    }
+}
+
+fn decode<const N: usize>(rx: &ByteDeque<N>, end: usize, dst: &mut [u8]) -> Result<usize, SlipError> {
+    let mut out = 0;
+    let mut escaped = false;
+    for i in 0..end {
+        let b = match (escaped, rx[i]) {
+            (false, ESC) => {
+                escaped = true;
+                continue;
+            }
+            (false, b) => b,
+            (true, ESC_END) => END,
+            (true, ESC_ESC) => ESC,
+            (true, _) => return Err(SlipError::Corrupt),
+        };
+        escaped = false;
+        *dst.get_mut(out).ok_or(SlipError::TooLong)? = b;
+        out += 1;
+    }
+    if escaped {
+        Err(SlipError::Corrupt)
+    } else {
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_test() {
+        let mut link = ByteDeque::<64>::new();
+        let frames: [&[u8]; 4] = [b"plain", &[END, ESC, ESC_END, ESC_ESC], &[ESC, ESC], &[1]];
+        for frame in frames {
+            let written = encode_into(frame, &mut link).unwrap();
+            assert_eq!(written, encoded_len(frame));
+        }
+        let mut decoded = [0; 8];
+        for frame in frames {
+            assert_eq!(decode_from(&mut link, &mut decoded), Some(Ok(frame.len())));
+            assert_eq!(&decoded[..frame.len()], frame);
+        }
+        assert_eq!(decode_from(&mut link, &mut decoded), None);
+        assert!(link.is_empty());
+    }
+
+    #[test]
+    fn resync_test() {
+        let mut link = ByteDeque::<32>::new();
+        link.write(&[0x33, ESC, 0x44, END]);
+        link.write(&[0x55, ESC, END]);
+        encode_into(&[0x66, 0x77], &mut link).unwrap();
+        assert_eq!(encode_into(&[0; 32], &mut link), Err(SlipError::NoSpace));
+
+        let mut decoded = [0; 1];
+        assert_eq!(decode_from(&mut link, &mut decoded), Some(Err(SlipError::Corrupt)));
+        assert_eq!(decode_from(&mut link, &mut decoded), Some(Err(SlipError::Corrupt)));
+        assert_eq!(decode_from(&mut link, &mut decoded), Some(Err(SlipError::TooLong)));
+        assert_eq!(decode_from(&mut link, &mut decoded), None);
+    }
+
+    #[test]
+    fn overrun_test() {
+        let mut link = ByteDeque::<8>::new();
+        link.write(&[0x55; 8]);
+        let mut decoded = [0; 4];
+        assert_eq!(decode_from(&mut link, &mut decoded), Some(Err(SlipError::Overrun)));
+        assert!(link.is_empty());
+        encode_into(b"ok", &mut link).unwrap();
+        assert_eq!(decode_from(&mut link, &mut decoded), Some(Ok(2)));
+        assert_eq!(&decoded[..2], b"ok");
+    }
+}
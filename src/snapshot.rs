@@ -0,0 +1,163 @@
+//! Saving deque contents to a byte buffer and restoring them later, so queued data can
+//! be persisted to EEPROM or FRAM across a power cycle.
+//!
+//! A snapshot holds the element count as a little-endian `u32`, then each element as
+//! encoded by [`SnapshotElement`], then a CRC-32 of everything before it.
+//!
+//! ```
+//! use bare_metal_deque::BareMetalDeque;
+//!
+//! let mut q = BareMetalDeque::<u16, 8>::new();
+//! q.push_back(300);
+//! q.push_back(7);
+//!
+//! let mut eeprom = [0; 32];
+//! let len = q.save_to(&mut eeprom).unwrap();
+//! assert_eq!(len, 4 + 2 * 2 + 4);
+//!
+//! let restored = BareMetalDeque::<u16, 8>::restore_from(&eeprom[..len]).unwrap();
+//! assert_eq!(restored.iter().copied().collect::<Vec<_>>(), vec![300, 7]);
+//! ```
+
+use crate::BareMetalDeque;
+use crate::crc::{Crc, Crc32};
+
+const LEN_BYTES: usize = 4;
+const CRC_BYTES: usize = 4;
+
+/// Element types with a fixed-size byte encoding.
+pub trait SnapshotElement: Sized {
+    /// Number of bytes in the encoding.
+    const SIZE: usize;
+
+    /// Encodes `self` into `dst`, which is exactly `SIZE` bytes long.
+    fn save(&self, dst: &mut [u8]);
+
+    /// Decodes a value from `src`, which is exactly `SIZE` bytes long, or returns `None`
+    /// if the bytes do not represent a valid value.
+    fn restore(src: &[u8]) -> Option<Self>;
+}
+
+macro_rules! impl_snapshot_element {
+    ($($t:ty),*) => {
+        $(impl SnapshotElement for $t {
+            const SIZE: usize = core::mem::size_of::<$t>();
+
+            fn save(&self, dst: &mut [u8]) {
+                dst.copy_from_slice(&self.to_le_bytes());
+            }
+
+            fn restore(src: &[u8]) -> Option<Self> {
+                src.try_into().ok().map(<$t>::from_le_bytes)
+            }
+        })*
+    };
+}
+
+impl_snapshot_element!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl SnapshotElement for bool {
+    const SIZE: usize = 1;
+
+    fn save(&self, dst: &mut [u8]) {
+        dst[0] = *self as u8;
+    }
+
+    fn restore(src: &[u8]) -> Option<Self> {
+        match src[0] {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// The destination buffer cannot hold the snapshot.
+    BufferTooSmall,
+    /// The snapshot holds more elements than the deque can store.
+    TooManyElements,
+    /// The snapshot is truncated, fails its CRC, or holds an invalid element.
+    Corrupt,
+}
+
+impl<T: Copy + Default + SnapshotElement, const MAX_STORED: usize> BareMetalDeque<T, MAX_STORED> {
+    /// Number of bytes `save_to()` will write for the current contents.
+    pub fn snapshot_len(&self) -> usize {
+        LEN_BYTES + self.len() * T::SIZE + CRC_BYTES
+    }
+
+    /// Writes a snapshot of the logical contents to the start of `dst`, returning its length.
+    pub fn save_to(&self, dst: &mut [u8]) -> Result<usize, SnapshotError> {
+        let len = self.snapshot_len();
+        if dst.len() < len {
+            return Err(SnapshotError::BufferTooSmall);
+        }
+        dst[..LEN_BYTES].copy_from_slice(&(self.len() as u32).to_le_bytes());
+        for (value, chunk) in self.iter().zip(dst[LEN_BYTES..].chunks_exact_mut(T::SIZE)) {
+            value.save(chunk);
+        }
+        let crc_at = len - CRC_BYTES;
+        let crc = Crc32::checksum(dst[..crc_at].iter().copied());
+        dst[crc_at..len].copy_from_slice(&crc.to_le_bytes());
+        Ok(len)
+    }
+
+    /// Rebuilds a deque from a snapshot written by `save_to()`. Bytes after the snapshot
+    /// are ignored.
+    pub fn restore_from(src: &[u8]) -> Result<Self, SnapshotError> {
+        let count_bytes = src.get(..LEN_BYTES).ok_or(SnapshotError::Corrupt)?;
+        let count = u32::from_le_bytes(count_bytes.try_into().unwrap()) as usize;
+        let len = count
+            .checked_mul(T::SIZE)
+            .and_then(|n| n.checked_add(LEN_BYTES + CRC_BYTES))
+            .ok_or(SnapshotError::Corrupt)?;
+        if len > src.len() {
+            return Err(SnapshotError::Corrupt);
+        }
+        let crc_at = len - CRC_BYTES;
+        let crc = Crc32::checksum(src[..crc_at].iter().copied());
+        if src[crc_at..len] != crc.to_le_bytes() {
+            return Err(SnapshotError::Corrupt);
+        }
+        if count > MAX_STORED {
+            return Err(SnapshotError::TooManyElements);
+        }
+
+        let mut result = Self::new();
+        for chunk in src[LEN_BYTES..crc_at].chunks_exact(T::SIZE) {
+            result.push_back(T::restore(chunk).ok_or(SnapshotError::Corrupt)?);
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_test() {
+        let mut q = BareMetalDeque::<i32, 4>::new();
+        for x in [-1, 2, -3, 4] {
+            q.push_back(x);
+        }
+        q.pop_front();
+        q.push_back(5);
+
+        let mut buf = [0; 64];
+        assert_eq!(q.save_to(&mut buf[..10]), Err(SnapshotError::BufferTooSmall));
+        let len = q.save_to(&mut buf).unwrap();
+        assert_eq!(len, q.snapshot_len());
+
+        let restored = BareMetalDeque::<i32, 4>::restore_from(&buf).unwrap();
+        assert_eq!(restored.iter().copied().collect::<Vec<_>>(), vec![2, -3, 4, 5]);
+        assert_eq!(BareMetalDeque::<i32, 3>::restore_from(&buf).unwrap_err(), SnapshotError::TooManyElements);
+        assert_eq!(BareMetalDeque::<i32, 4>::restore_from(&buf[..len - 1]).unwrap_err(), SnapshotError::Corrupt);
+
+        buf[5] ^= 1;
+        assert_eq!(BareMetalDeque::<i32, 4>::restore_from(&buf).unwrap_err(), SnapshotError::Corrupt);
+        assert_eq!(BareMetalDeque::<i32, 4>::restore_from(&[0xFF; 64]).unwrap_err(), SnapshotError::Corrupt);
+    }
+}
@@ -0,0 +1,122 @@
+//! A ring of `u32` samples that one writer, such as an interrupt handler, updates without
+//! ever waiting, while readers take consistent copies, as for a telemetry task sampling a
+//! high-rate buffer.
+//!
+//! It works like a seqlock. The writer makes a sequence counter odd while it updates
+//! the ring and even again afterwards. A reader copies the ring and retries if the
+//! counter was odd or changed meanwhile. Only atomic loads and stores are used, so it
+//! works on cores without compare-and-swap, such as Cortex-M0.
+//!
+//! ```
+//! use bare_metal_deque::SnapshotRing;
+//!
+//! static ADC: SnapshotRing<4> = SnapshotRing::new();
+//!
+//! // In the interrupt handler:
+//! for sample in [10, 20, 30, 40, 50] {
+//!     ADC.push(sample);
+//! }
+//!
+//! // In a telemetry task:
+//! assert_eq!(ADC.snapshot().view(..), [20, 30, 40, 50]);
+//! ```
+
+use core::sync::atomic::{AtomicU32, AtomicUsize, Ordering, fence};
+
+use crate::BareMetalDeque;
+
+#[derive(Debug)]
+pub struct SnapshotRing<const N: usize> {
+    slots: [AtomicU32; N],
+    sequence: AtomicU32,
+    /// Index in `slots` of the oldest sample.
+    start: AtomicUsize,
+    len: AtomicUsize,
+}
+
+impl<const N: usize> Default for SnapshotRing<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> SnapshotRing<N> {
+    pub const fn new() -> Self {
+        Self {
+            slots: [const { AtomicU32::new(0) }; N],
+            sequence: AtomicU32::new(0),
+            start: AtomicUsize::new(0),
+            len: AtomicUsize::new(0),
+        }
+    }
+
+    /// Appends `sample`, overwriting the oldest one if the ring is full. Only one context
+    /// may push; concurrent pushes from several contexts can corrupt the ring.
+    pub fn push(&self, sample: u32) {
+        if N == 0 {
+            return;
+        }
+        let sequence = self.sequence.load(Ordering::Relaxed);
+        self.sequence.store(sequence.wrapping_add(1), Ordering::Relaxed);
+        fence(Ordering::Release);
+
+        let (start, len) = (self.start.load(Ordering::Relaxed), self.len.load(Ordering::Relaxed));
+        self.slots[(start + len) % N].store(sample, Ordering::Relaxed);
+        if len == N {
+            self.start.store((start + 1) % N, Ordering::Relaxed);
+        } else {
+            self.len.store(len + 1, Ordering::Relaxed);
+        }
+
+        self.sequence.store(sequence.wrapping_add(2), Ordering::Release);
+    }
+
+    /// Makes one attempt to copy the samples, oldest first. Returns `None` if the writer
+    /// was active during the copy.
+    pub fn try_snapshot(&self) -> Option<BareMetalDeque<u32, N>> {
+        let before = self.sequence.load(Ordering::Acquire);
+        if before % 2 == 1 {
+            return None;
+        }
+        let start = self.start.load(Ordering::Relaxed);
+        let len = self.len.load(Ordering::Relaxed).min(N);
+        let mut copy = BareMetalDeque::new();
+        for i in 0..len {
+            copy.push_back(self.slots[(start + i) % N].load(Ordering::Relaxed));
+        }
+        fence(Ordering::Acquire);
+        (self.sequence.load(Ordering::Relaxed) == before).then_some(copy)
+    }
+
+    /// Copies the samples, oldest first, retrying until the copy is consistent. Never
+    /// blocks the writer, but a reader that is preempted by writes over and over may
+    /// spin; use `try_snapshot()` to bound the attempts.
+    pub fn snapshot(&self) -> BareMetalDeque<u32, N> {
+        loop {
+            if let Some(copy) = self.try_snapshot() {
+                return copy;
+            }
+            core::hint::spin_loop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concurrent_snapshot_test() {
+        let ring = SnapshotRing::<8>::new();
+        std::thread::scope(|s| {
+            s.spawn(|| (1..=100_000).for_each(|x| ring.push(x)));
+            for _ in 0..1_000 {
+                let copy = ring.snapshot();
+                let consecutive = copy.iter().zip(copy.iter().skip(1)).all(|(a, b)| b - a == 1);
+                assert!(consecutive, "{:?}", copy);
+            }
+        });
+        assert_eq!(ring.snapshot().back(), Some(100_000));
+        SnapshotRing::<0>::new().push(1);
+    }
+}
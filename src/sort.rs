@@ -0,0 +1,312 @@
+//! Sorting, ordering queries, selection and partitioning over the logical contents, for
+//! rings kept sorted and for windowed medians and percentiles.
+//!
+//! ```
+//! use bare_metal_deque::deque;
+//!
+//! let mut window = deque![40, 10, 50, 20, 30; cap = 5];
+//! assert!(!window.is_sorted());
+//! assert_eq!(window.select_nth_unstable(2), 30); // the median
+//! ```
+
+use core::cmp::Ordering;
+
+use crate::{BareMetalDeque, CapacityError, FullPolicy};
+
+impl<T: Copy + Clone + Default, const MAX_STORED: usize> BareMetalDeque<T, MAX_STORED> {
+    /// Reorders the contents so the element at logical index `k` is the one that would be
+    /// there if they were sorted, with no greater element before it and no lesser one after
+    /// it, and returns that element. Runs in linear time on average. Panics if `k >= len()`.
+    pub fn select_nth_unstable(&mut self, k: usize) -> T
+    where
+        T: Ord,
+    {
+        self.select_nth_unstable_by(k, T::cmp)
+    }
+
+    /// Like `select_nth_unstable()`, ordering elements by `compare`.
+    pub fn select_nth_unstable_by<F: FnMut(&T, &T) -> Ordering>(&mut self, k: usize, compare: F) -> T {
+        *self.make_contiguous().select_nth_unstable_by(k, compare).1
+    }
+
+    /// The `K` smallest elements in ascending order, found in one pass without reordering
+    /// the deque, or `None` if there are fewer than `K`. Intended for small `K`.
+    pub fn k_smallest<const K: usize>(&self) -> Option<[T; K]>
+    where
+        T: Ord,
+    {
+        self.k_smallest_by(T::cmp)
+    }
+
+    /// The `K` largest elements in descending order, or `None` if there are fewer than `K`.
+    pub fn k_largest<const K: usize>(&self) -> Option<[T; K]>
+    where
+        T: Ord,
+    {
+        self.k_smallest_by(|a, b| b.cmp(a))
+    }
+
+    /// Like `k_smallest()`, ordering elements by `compare`. Among equal elements, the
+    /// earlier ones are kept.
+    pub fn k_smallest_by<const K: usize, F: FnMut(&T, &T) -> Ordering>(&self, mut compare: F) -> Option<[T; K]> {
+        if self.size < K {
+            return None;
+        }
+        let mut best = [T::default(); K];
+        for (seen, value) in self.iter().enumerate() {
+            let mut j = seen.min(K);
+            while j > 0 && compare(&best[j - 1], value) == Ordering::Greater {
+                if j < K {
+                    best[j] = best[j - 1];
+                }
+                j -= 1;
+            }
+            if j < K {
+                best[j] = *value;
+            }
+        }
+        Some(best)
+    }
+
+    /// Sorts the contents in place with insertion sort, keeping equal elements in their
+    /// original order. Runs in near-linear time when elements are only slightly out of
+    /// order, such as packets arriving a little late, and needs no recursion or buffer.
+    pub fn sort_stable_insertion(&mut self)
+    where
+        T: Ord,
+    {
+        self.sort_stable_insertion_by(T::cmp)
+    }
+
+    /// Like `sort_stable_insertion()`, ordering elements by `compare`.
+    pub fn sort_stable_insertion_by<F: FnMut(&T, &T) -> Ordering>(&mut self, mut compare: F) {
+        for i in 1..self.size {
+            let value = self.array[self.physical(i)];
+            let mut j = i;
+            while j > 0 && compare(&self.array[self.physical(j - 1)], &value) == Ordering::Greater {
+                let (dst, src) = (self.physical(j), self.physical(j - 1));
+                self.array[dst] = self.array[src];
+                j -= 1;
+            }
+            let dst = self.physical(j);
+            self.array[dst] = value;
+        }
+    }
+
+    /// Reorders the contents so every element matching `pred` precedes every element that
+    /// does not, and returns the number that match. Does not preserve the relative order
+    /// within either group.
+    pub fn partition_in_place<F: FnMut(&T) -> bool>(&mut self, mut pred: F) -> usize {
+        let (mut lo, mut hi) = (0, self.size);
+        loop {
+            while lo < hi && pred(&self.array[self.physical(lo)]) {
+                lo += 1;
+            }
+            while lo < hi && !pred(&self.array[self.physical(hi - 1)]) {
+                hi -= 1;
+            }
+            if lo == hi {
+                return lo;
+            }
+            let (a, b) = (self.physical(lo), self.physical(hi - 1));
+            self.array.swap(a, b);
+            lo += 1;
+            hi -= 1;
+        }
+    }
+
+    /// Moves the elements matching `pred` into one new deque and the rest into another,
+    /// keeping their order, as when separating control frames from data frames. A
+    /// destination that fills up is handled according to `policy`.
+    pub fn partition_into<const A: usize, const B: usize, F: FnMut(&T) -> bool>(
+        self,
+        mut pred: F,
+        policy: FullPolicy,
+    ) -> (BareMetalDeque<T, A>, BareMetalDeque<T, B>) {
+        let mut matching = BareMetalDeque::new();
+        let mut rest = BareMetalDeque::new();
+        for value in self.iter().copied() {
+            let _ = if pred(&value) {
+                matching.push_back_with_policy(value, policy)
+            } else {
+                rest.push_back_with_policy(value, policy)
+            };
+        }
+        (matching, rest)
+    }
+
+    /// Merges this sorted deque with sorted `other` into a new sorted deque, as when fusing
+    /// two time-ordered streams. Among equal elements, those from `self` come first. Fails
+    /// if the combined contents do not fit.
+    pub fn merge_sorted<const N: usize, const M: usize>(
+        self,
+        mut other: BareMetalDeque<T, N>,
+    ) -> Result<BareMetalDeque<T, M>, CapacityError>
+    where
+        T: Ord,
+    {
+        if self.size > M {
+            return Err(CapacityError);
+        }
+        let mut result = BareMetalDeque::new();
+        result.extend_from_slice(self.as_slices().0);
+        result.extend_from_slice(self.as_slices().1);
+        result.merge_from(&mut other)?;
+        Ok(result)
+    }
+
+    /// Merges all of sorted `other` into this sorted deque in place, leaving `other` empty.
+    /// Among equal elements, those already in `self` come first. Fails, leaving both
+    /// unchanged, if the combined contents do not fit.
+    pub fn merge_from<const N: usize>(&mut self, other: &mut BareMetalDeque<T, N>) -> Result<(), CapacityError>
+    where
+        T: Ord,
+    {
+        let (mut i, mut j) = (self.size, other.size);
+        if j > MAX_STORED - i {
+            return Err(CapacityError);
+        }
+        self.size += j;
+        self.record_pushes(j);
+        // Fill from the back, so each element is moved at most once.
+        while j > 0 {
+            let dst = self.physical(i + j - 1);
+            let theirs = other.array[other.physical(j - 1)];
+            if i > 0 && self.array[self.physical(i - 1)] > theirs {
+                self.array[dst] = self.array[self.physical(i - 1)];
+                i -= 1;
+            } else {
+                self.array[dst] = theirs;
+                j -= 1;
+            }
+        }
+        other.discard(other.size);
+        self.debug_check_invariants();
+        Ok(())
+    }
+
+    /// Whether the contents are in non-decreasing order, front to back.
+    pub fn is_sorted(&self) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.is_sorted_by(|a, b| a <= b)
+    }
+
+    /// Whether `in_order(a, b)` holds for every pair of adjacent elements `a`, `b`.
+    pub fn is_sorted_by<F: FnMut(&T, &T) -> bool>(&self, mut in_order: F) -> bool {
+        let (front, back) = self.as_slices();
+        front.is_sorted_by(&mut in_order)
+            && back.is_sorted_by(&mut in_order)
+            && match (front.last(), back.first()) {
+                (Some(a), Some(b)) => in_order(a, b),
+                _ => true,
+            }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select_nth_test() {
+        let mut q = BareMetalDeque::<i32, 7>::new();
+        q.extend_from_slice(&[0, 0, 0, 0, 0]);
+        q.discard(5);
+        q.extend_from_slice(&[5, -3, 9, 1, 7, 0]);
+        assert_eq!(q.select_nth_unstable(0), -3);
+        assert_eq!(q.select_nth_unstable(5), 9);
+        assert_eq!(q.select_nth_unstable_by(1, |a, b| b.cmp(a)), 7);
+        assert_eq!(q.view(..2).iter().filter(|x| **x >= 7).count(), 2);
+    }
+
+    #[test]
+    fn k_smallest_test() {
+        let mut q = BareMetalDeque::<(i8, char), 6>::new();
+        q.extend_from_slice(&[(0, ' '); 4]);
+        q.discard(4);
+        q.extend_from_slice(&[(-60, 'a'), (-80, 'b'), (-40, 'c'), (-60, 'd'), (-90, 'e')]);
+        assert_eq!(q.k_largest(), Some([(-40, 'c'), (-60, 'd')]));
+        assert_eq!(q.k_smallest_by(|a, b| a.0.cmp(&b.0)), Some([(-90, 'e'), (-80, 'b'), (-60, 'a')]));
+        assert_eq!(q.k_smallest::<6>(), None);
+        assert_eq!(q.k_smallest(), Some([]));
+    }
+
+    #[test]
+    fn insertion_sort_test() {
+        let mut q = BareMetalDeque::<(u8, char), 5>::new();
+        q.extend_from_slice(&[(0, ' '); 3]);
+        q.discard(3);
+        q.extend_from_slice(&[(2, 'a'), (1, 'b'), (3, 'c'), (2, 'd'), (1, 'e')]);
+        q.sort_stable_insertion_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(q.view(..), [(1, 'b'), (1, 'e'), (2, 'a'), (2, 'd'), (3, 'c')]);
+        q.sort_stable_insertion();
+        assert!(q.is_sorted());
+    }
+
+    #[test]
+    fn partition_test() {
+        let mut q = BareMetalDeque::<u8, 6>::new();
+        q.extend_from_slice(&[0, 0, 0, 0]);
+        q.discard(4);
+        q.extend_from_slice(&[1, 8, 3, 6, 5, 2]);
+        assert_eq!(q.partition_in_place(|x| x % 2 == 0), 3);
+        assert!(q.view(..3).iter().all(|x| x % 2 == 0));
+        assert!(q.view(3..).iter().all(|x| x % 2 == 1));
+        assert_eq!(q.partition_in_place(|_| false), 0);
+        assert_eq!(q.partition_in_place(|_| true), 6);
+    }
+
+    #[test]
+    fn partition_into_test() {
+        let mut q = BareMetalDeque::<u8, 6>::new();
+        q.extend_from_slice(&[0, 0, 0]);
+        q.discard(3);
+        q.extend_from_slice(&[1, 8, 3, 6, 5, 2]);
+        let (even, odd) = q.partition_into::<2, 3, _>(|x| x % 2 == 0, FullPolicy::OverwriteOldest);
+        assert_eq!(even.view(..), [6, 2]);
+        assert_eq!(odd.view(..), [1, 3, 5]);
+        let (even, odd) = q.partition_into::<2, 3, _>(|x| x % 2 == 0, FullPolicy::Reject);
+        assert_eq!(even.view(..), [8, 6]);
+        assert_eq!(odd.len(), 3);
+    }
+
+    #[test]
+    fn merge_test() {
+        let mut a = BareMetalDeque::<(u8, char), 5>::new();
+        a.extend_from_slice(&[(0, ' '); 4]);
+        a.discard(4);
+        a.extend_from_slice(&[(1, 'a'), (4, 'a'), (6, 'a')]);
+        let mut b = BareMetalDeque::<(u8, char), 3>::new();
+        b.extend_from_slice(&[(0, 'b'), (4, 'a'), (9, 'b')]);
+        let merged = a.merge_sorted::<3, 6>(b).unwrap();
+        assert!(merged.is_sorted());
+        assert_eq!(merged.view(..3), [(0, 'b'), (1, 'a'), (4, 'a')]);
+        assert_eq!(a.merge_sorted::<3, 5>(b), Err(CapacityError));
+
+        let mut c = BareMetalDeque::<(u8, char), 2>::new();
+        c.extend_from_slice(&[(2, 'c'), (7, 'c')]);
+        assert_eq!(a.merge_from(&mut c), Ok(()));
+        assert!(c.is_empty());
+        assert_eq!(a.view(..), [(1, 'a'), (2, 'c'), (4, 'a'), (6, 'a'), (7, 'c')]);
+        assert_eq!(a.merge_from(&mut b), Err(CapacityError));
+        assert_eq!(b.len(), 3);
+    }
+
+    #[test]
+    fn is_sorted_test() {
+        let mut q = BareMetalDeque::<u8, 4>::new();
+        assert!(q.is_sorted());
+        q.extend_from_slice(&[0, 0, 1, 2]);
+        q.discard(2);
+        q.push_back(2);
+        assert!(q.is_sorted());
+        q.push_back(0);
+        assert!(!q.is_sorted());
+        assert!(!q.is_sorted_by(|a, b| a < b));
+        q.pop_back();
+        assert!(!q.is_sorted_by(|a, b| a < b));
+        assert!(q.is_sorted_by(|a, b| a >= b || *b == 2));
+    }
+}
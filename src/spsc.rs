@@ -0,0 +1,224 @@
+//! A lock-free single-producer, single-consumer queue, for passing data between an
+//! interrupt handler and the main loop, or between the cores of a dual-core part, with
+//! neither side ever waiting on a lock.
+//!
+//! [`SpscQueue::split`] hands out a [`Producer`] and a [`Consumer`] that share atomic head
+//! and tail indices. Each half also keeps a cached copy of the other side's index and
+//! reloads the shared atomic only when the cached value makes the queue look full (for
+//! the producer) or empty (for the consumer), so in steady state each operation touches
+//! just one shared cache line. Only atomic loads and stores are used, so it works on cores
+//! without compare-and-swap, such as Cortex-M0.
+//!
+//! ```
+//! use bare_metal_deque::SpscQueue;
+//!
+//! let mut queue = SpscQueue::<u8, 4>::new();
+//! let (mut tx, mut rx) = queue.split();
+//! std::thread::scope(|s| {
+//!     s.spawn(move || b"hi".iter().for_each(|b| while tx.push(*b).is_err() {}));
+//! });
+//! assert_eq!(rx.pop(), Some(b'h'));
+//! assert_eq!(rx.pop(), Some(b'i'));
+//! assert_eq!(rx.pop(), None);
+//! ```
+
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{A64, Aligned, CapacityError};
+
+/// Storage shared by a [`Producer`] and a [`Consumer`].
+///
+/// The head and tail run from 0 to `2 * N - 1` rather than 0 to `N - 1`, so that a full
+/// queue and an empty one have different indices and all `N` slots can be used. Each
+/// index sits on its own 64-byte cache line, so a store by one side does not evict the
+/// line the other side is writing.
+pub struct SpscQueue<T, const N: usize> {
+    slots: [UnsafeCell<MaybeUninit<T>>; N],
+    /// Index of the next element to pop, written only by the consumer.
+    head: Aligned<A64, AtomicUsize>,
+    /// Index of the next slot to push into, written only by the producer.
+    tail: Aligned<A64, AtomicUsize>,
+}
+
+// SAFETY: the slots are only accessed through the halves returned by `split()`, which
+// borrow the queue mutably, so there is at most one producer and one consumer. The
+// producer writes a slot only while it is outside the consumer's range, and publishes it
+// with a release store of `tail`; the consumer reads it only after an acquire load of
+// `tail` shows it, and hands it back with a release store of `head`.
+unsafe impl<T: Send, const N: usize> Sync for SpscQueue<T, N> {}
+
+impl<T: Copy, const N: usize> Default for SpscQueue<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Copy, const N: usize> SpscQueue<T, N> {
+    pub const fn new() -> Self {
+        Self {
+            slots: [const { UnsafeCell::new(MaybeUninit::uninit()) }; N],
+            head: Aligned::new(AtomicUsize::new(0)),
+            tail: Aligned::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Number of elements queued. Exact only while the queue is not split.
+    pub fn len(&self) -> usize {
+        distance(self.head.load(Ordering::Acquire), self.tail.load(Ordering::Acquire), N)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Divides the queue into its producing and consuming halves, which may be moved to
+    /// different contexts. Splitting again after the halves are dropped continues with
+    /// the elements already queued.
+    pub fn split(&mut self) -> (Producer<'_, T, N>, Consumer<'_, T, N>) {
+        let head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Relaxed);
+        let queue = &*self;
+        (
+            Producer { queue, tail, cached_head: head },
+            Consumer { queue, head, cached_tail: tail },
+        )
+    }
+}
+
+/// The pushing half of a split [`SpscQueue`].
+pub struct Producer<'a, T, const N: usize> {
+    queue: &'a SpscQueue<T, N>,
+    tail: usize,
+    /// The consumer's `head` when last loaded. The real head is never behind it.
+    cached_head: usize,
+}
+
+impl<T: Copy, const N: usize> Producer<'_, T, N> {
+    /// Appends `value`, failing if the queue is full.
+    pub fn push(&mut self, value: T) -> Result<(), CapacityError> {
+        if distance(self.cached_head, self.tail, N) == N {
+            self.cached_head = self.queue.head.load(Ordering::Acquire);
+            if distance(self.cached_head, self.tail, N) == N {
+                return Err(CapacityError);
+            }
+        }
+        let slot = &self.queue.slots[slot(self.tail, N)];
+        // SAFETY: the slot lies outside the range the consumer may read until the store
+        // to `tail` below publishes it, and only this producer writes slots.
+        unsafe { (*slot.get()).write(value) };
+        self.tail = advance(self.tail, N);
+        self.queue.tail.store(self.tail, Ordering::Release);
+        Ok(())
+    }
+
+    /// Whether the queue is full, after reloading the consumer's index.
+    pub fn is_full(&mut self) -> bool {
+        self.cached_head = self.queue.head.load(Ordering::Acquire);
+        distance(self.cached_head, self.tail, N) == N
+    }
+}
+
+/// The popping half of a split [`SpscQueue`].
+pub struct Consumer<'a, T, const N: usize> {
+    queue: &'a SpscQueue<T, N>,
+    head: usize,
+    /// The producer's `tail` when last loaded. The real tail is never behind it.
+    cached_tail: usize,
+}
+
+impl<T: Copy, const N: usize> Consumer<'_, T, N> {
+    /// Removes the oldest element, or returns `None` if the queue is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        let value = self.peek()?;
+        self.head = advance(self.head, N);
+        self.queue.head.store(self.head, Ordering::Release);
+        Some(value)
+    }
+
+    /// The oldest element, without removing it.
+    pub fn peek(&mut self) -> Option<T> {
+        if self.head == self.cached_tail {
+            self.cached_tail = self.queue.tail.load(Ordering::Acquire);
+            if self.head == self.cached_tail {
+                return None;
+            }
+        }
+        let slot = &self.queue.slots[slot(self.head, N)];
+        // SAFETY: the producer published this slot before the `tail` that was loaded
+        // above, and does not write it again until the consumer moves `head` past it.
+        Some(unsafe { (*slot.get()).assume_init() })
+    }
+
+    /// Number of elements ready to pop, after reloading the producer's index.
+    pub fn len(&mut self) -> usize {
+        self.cached_tail = self.queue.tail.load(Ordering::Acquire);
+        distance(self.head, self.cached_tail, N)
+    }
+
+    pub fn is_empty(&mut self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Number of elements from `head` up to `tail`, both in `0..2 * n`.
+fn distance(head: usize, tail: usize, n: usize) -> usize {
+    if tail >= head { tail - head } else { tail + 2 * n - head }
+}
+
+fn advance(index: usize, n: usize) -> usize {
+    let next = index + 1;
+    if next == 2 * n { 0 } else { next }
+}
+
+fn slot(index: usize, n: usize) -> usize {
+    if index >= n { index - n } else { index }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn concurrent_spsc_test() {
+        let mut queue = SpscQueue::<u32, 5>::new();
+        let (mut tx, mut rx) = queue.split();
+        std::thread::scope(|s| {
+            s.spawn(move || {
+                for x in 1..=100_000 {
+                    while tx.push(x).is_err() {
+                        std::thread::yield_now();
+                    }
+                }
+            });
+            let mut expected = 1;
+            while expected <= 100_000 {
+                match rx.pop() {
+                    Some(x) => {
+                        assert_eq!(x, expected);
+                        expected += 1;
+                    }
+                    None => std::thread::yield_now(),
+                }
+            }
+            assert_eq!(rx.pop(), None);
+        });
+
+        let (mut tx, mut rx) = queue.split();
+        for x in 0..5 {
+            tx.push(x).unwrap();
+        }
+        assert!(tx.is_full());
+        assert_eq!(tx.push(5), Err(CapacityError));
+        assert_eq!((rx.len(), rx.peek()), (5, Some(0)));
+        assert_eq!(rx.pop(), Some(0));
+        assert_eq!(queue.len(), 4);
+        assert!(SpscQueue::<u8, 0>::new().split().0.push(1).is_err());
+        assert!((&*queue.head as *const AtomicUsize as usize).abs_diff(&*queue.tail as *const AtomicUsize as usize) >= 64);
+    }
+}
@@ -0,0 +1,91 @@
+//! Lifetime operation counters, behind the `stats` feature, so deployed devices can report
+//! queue health, such as how often data was dropped, in heartbeat messages.
+//!
+//! ```
+//! use bare_metal_deque::BareMetalDeque;
+//!
+//! let mut q = BareMetalDeque::<u8, 2>::new();
+//! q.extend_from_slice(b"abc");
+//! q.pop_front();
+//! let stats = q.stats();
+//! assert_eq!((stats.pushes, stats.pops, stats.rejected, stats.max_len), (2, 1, 1, 2));
+//! ```
+
+use crate::BareMetalDeque;
+
+/// Counts of elements through a deque since it was created or its stats were reset. The
+/// counts wrap on overflow, so subtract successive readings with `wrapping_sub`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct QueueStats {
+    /// Elements added.
+    pub pushes: u32,
+    /// Elements removed, including evicted ones.
+    pub pops: u32,
+    /// Elements not added because the deque was full.
+    pub rejected: u32,
+    /// Elements removed to make room for newer ones.
+    pub evicted: u32,
+    /// The largest length the deque has reached.
+    pub max_len: usize,
+}
+
+impl QueueStats {
+    pub const fn new() -> Self {
+        Self { pushes: 0, pops: 0, rejected: 0, evicted: 0, max_len: 0 }
+    }
+
+    // These are inlined so `no-panic` builds can see that the push and pop paths calling
+    // them cannot unwind.
+    #[inline]
+    pub(crate) fn record_pushes(&mut self, n: usize, len: usize) {
+        self.pushes = self.pushes.wrapping_add(n as u32);
+        self.max_len = self.max_len.max(len);
+    }
+
+    #[inline]
+    pub(crate) fn record_pops(&mut self, n: usize) {
+        self.pops = self.pops.wrapping_add(n as u32);
+    }
+
+    #[inline]
+    pub(crate) fn record_rejects(&mut self, n: usize) {
+        self.rejected = self.rejected.wrapping_add(n as u32);
+    }
+
+    #[inline]
+    pub(crate) fn record_evictions(&mut self, n: usize) {
+        self.evicted = self.evicted.wrapping_add(n as u32);
+    }
+}
+
+impl<T: Default, const MAX_STORED: usize> BareMetalDeque<T, MAX_STORED> {
+    pub fn stats(&self) -> QueueStats {
+        self.stats
+    }
+
+    /// Zeroes the counters, starting `max_len` over from the current length.
+    pub fn reset_stats(&mut self) {
+        self.stats = QueueStats { max_len: self.size, ..QueueStats::new() };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_test() {
+        let mut q = BareMetalDeque::<u8, 3>::new();
+        q.push_back(5);
+        q.push_front(1);
+        q.push_back_evicting_lowest(3, |x| *x);
+        assert_eq!(q.push_back_evicting_lowest(0, |x| *x), Some(0));
+        assert_eq!(q.push_back_evicting_lowest(4, |x| *x), Some(1));
+        assert_eq!(q.push_front_slice(&[7]), Err(crate::CapacityError));
+        q.remove_range(1..);
+        let expected = QueueStats { pushes: 4, pops: 3, rejected: 2, evicted: 1, max_len: 3 };
+        assert_eq!(q.stats(), expected);
+        q.reset_stats();
+        assert_eq!(q.stats(), QueueStats { max_len: 1, ..QueueStats::new() });
+    }
+}
@@ -0,0 +1,106 @@
+//! A deque of timestamped values, for windows defined by age rather than count.
+//!
+//! Ticks are `u32` counter values, such as milliseconds since boot, and are compared with
+//! wrapping arithmetic, so windows keep working when the counter rolls over as long as
+//! they span less than half its range.
+//!
+//! ```
+//! use bare_metal_deque::TimestampedDeque;
+//!
+//! let mut presses = TimestampedDeque::<u8, 8>::new();
+//! presses.push(1_000, b'a');
+//! presses.push(1_400, b'b');
+//! presses.push(2_100, b'c');
+//! // Keep the last second.
+//! assert_eq!(presses.expire_older_than(2_100 - 1_000), 1);
+//! assert_eq!(presses.oldest(), Some((1_400, b'b')));
+//! ```
+
+use crate::BareMetalDeque;
+
+#[derive(Copy, Clone, Debug)]
+pub struct TimestampedDeque<T: Default, const MAX_STORED: usize> {
+    entries: BareMetalDeque<(u32, T), MAX_STORED>,
+}
+
+impl<T: Copy + Default, const MAX_STORED: usize> Default for TimestampedDeque<T, MAX_STORED> {
+    fn default() -> Self {
+        Self { entries: BareMetalDeque::new() }
+    }
+}
+
+impl<T: Copy + Default, const MAX_STORED: usize> TimestampedDeque<T, MAX_STORED> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Appends `value` stamped with `now`, which should not be earlier than the newest
+    /// entry's tick. If the deque is full, the oldest entry is dropped and returned.
+    pub fn push(&mut self, now: u32, value: T) -> Option<(u32, T)> {
+        if MAX_STORED == 0 {
+            return Some((now, value));
+        }
+        let evicted = if self.entries.is_full() { self.entries.pop_front() } else { None };
+        self.entries.push_back((now, value));
+        evicted
+    }
+
+    /// Drops entries stamped before `cutoff` from the front, returning how many were
+    /// dropped. Runs in time proportional to that number.
+    pub fn expire_older_than(&mut self, cutoff: u32) -> usize {
+        let mut expired = 0;
+        while let Some((tick, _)) = self.entries.front() {
+            if (tick.wrapping_sub(cutoff) as i32) >= 0 {
+                break;
+            }
+            self.entries.pop_front();
+            expired += 1;
+        }
+        expired
+    }
+
+    pub fn oldest(&self) -> Option<(u32, T)> {
+        self.entries.front()
+    }
+
+    pub fn newest(&self) -> Option<(u32, T)> {
+        self.entries.back()
+    }
+
+    /// The entries as `(tick, value)` pairs, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &(u32, T)> {
+        self.entries.iter()
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapping_expiry_test() {
+        let mut q = TimestampedDeque::<i16, 3>::new();
+        for (tick, v) in [(u32::MAX - 20, 1), (u32::MAX - 5, 2), (10, 3)] {
+            assert_eq!(q.push(tick, v), None);
+        }
+        assert_eq!(q.push(30, 4), Some((u32::MAX - 20, 1)));
+        assert_eq!(q.expire_older_than(u32::MAX - 5), 0);
+        assert_eq!(q.expire_older_than(5), 1);
+        assert_eq!(q.iter().map(|e| e.1).collect::<Vec<_>>(), vec![3, 4]);
+        assert_eq!(q.expire_older_than(31), 2);
+        assert!(q.is_empty());
+        assert_eq!(TimestampedDeque::<i16, 0>::new().push(1, 2), Some((1, 2)));
+    }
+}
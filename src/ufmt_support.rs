@@ -0,0 +1,43 @@
+//! [`ufmt`](https://docs.rs/ufmt) support, behind the `ufmt` feature, for targets that
+//! avoid `core::fmt` to save flash. A deque implements `uDebug` by listing its logical
+//! contents, and [`HexDump`] implements `uDisplay`.
+//!
+//! ```
+//! use bare_metal_deque::ByteDeque;
+//!
+//! let mut q = ByteDeque::<4>::new();
+//! q.write(b"AT");
+//! let mut out = String::new();
+//! ufmt::uwrite!(out, "{:?}", q).unwrap();
+//! assert_eq!(out, "[65, 84]");
+//! ```
+
+use ufmt::{Formatter, uDebug, uDisplay, uWrite};
+
+use crate::{BareMetalDeque, HexDump};
+
+impl<T: Copy + Default + uDebug, const MAX_STORED: usize> uDebug for BareMetalDeque<T, MAX_STORED> {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        f.debug_list()?.entries(self.iter())?.finish()
+    }
+}
+
+impl<const N: usize> uDisplay for HexDump<'_, N> {
+    fn fmt<W: uWrite + ?Sized>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error> {
+        self.for_each_line(|line| f.write_str(line))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ByteDeque;
+
+    #[test]
+    fn hex_dump_matches_core_fmt_test() {
+        let mut q = ByteDeque::<40>::new();
+        q.write(b"0123456789abcdefghijklmnopqrstuvwxyz\r\n");
+        let mut out = String::new();
+        ufmt::uwrite!(out, "{}", q.hex_dump()).unwrap();
+        assert_eq!(out, format!("{}", q.hex_dump()));
+    }
+}
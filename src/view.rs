@@ -0,0 +1,343 @@
+//! Views of part of a deque's contents. A view's elements may span the wrap point of the
+//! ring, which a plain slice cannot, so it holds up to two slices, as `as_slices()` returns.
+//!
+//! ```
+//! use bare_metal_deque::BareMetalDeque;
+//!
+//! let mut frame = BareMetalDeque::<u8, 8>::new();
+//! frame.write(b"\x02\x01hi!");
+//! let (header, payload) = frame.split_at(2);
+//! assert_eq!(header.get(0), Some(&2));
+//! assert_eq!(payload, *b"hi!");
+//!
+//! for b in frame.view_mut(2..4).iter_mut() {
+//!     *b = b.to_ascii_uppercase();
+//! }
+//! assert_eq!(frame.view(2..), *b"HI!");
+//! assert_eq!(&frame[..2], b"\x02\x01");
+//! ```
+
+use core::ops::{Bound, Index, IndexMut, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+
+use crate::BareMetalDeque;
+
+#[derive(Debug)]
+pub struct DequeView<'a, T> {
+    front: &'a [T],
+    back: &'a [T],
+}
+
+// Derived impls would needlessly require `T: Copy`.
+impl<T> Clone for DequeView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for DequeView<'_, T> {}
+
+impl<'a, T> DequeView<'a, T> {
+    pub(crate) fn new((front, back): (&'a [T], &'a [T])) -> Self {
+        Self { front, back }
+    }
+
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<&'a T> {
+        match index.checked_sub(self.front.len()) {
+            None => self.front.get(index),
+            Some(i) => self.back.get(i),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + 'a {
+        self.front.iter().chain(self.back)
+    }
+
+    /// Copies elements from the start of the view into `dst`, returning the number copied.
+    pub fn copy_to_slice(&self, dst: &mut [T]) -> usize
+    where
+        T: Copy,
+    {
+        let n = dst.len().min(self.len());
+        let first = n.min(self.front.len());
+        dst[..first].copy_from_slice(&self.front[..first]);
+        dst[first..n].copy_from_slice(&self.back[..n - first]);
+        n
+    }
+
+    /// Returns the elements as two contiguous slices, in order. The second slice is empty
+    /// unless the view spans the wrap point.
+    pub fn as_slices(&self) -> (&'a [T], &'a [T]) {
+        (self.front, self.back)
+    }
+
+    /// Divides the view into the first `index` elements and the rest. Panics if
+    /// `index > len()`.
+    pub fn split_at(&self, index: usize) -> (Self, Self) {
+        if index > self.len() {
+            panic!("Split index out of bounds");
+        }
+        match index.checked_sub(self.front.len()) {
+            None => {
+                let (a, b) = self.front.split_at(index);
+                (Self::new((a, &[])), Self::new((b, self.back)))
+            }
+            Some(i) => {
+                let (a, b) = self.back.split_at(i);
+                (Self::new((self.front, a)), Self::new((b, &[])))
+            }
+        }
+    }
+}
+
+impl<T: PartialEq> PartialEq for DequeView<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        segments_eq(self.as_slices(), other.as_slices())
+    }
+}
+
+impl<T: PartialEq> PartialEq<[T]> for DequeView<'_, T> {
+    fn eq(&self, other: &[T]) -> bool {
+        segments_eq(self.as_slices(), (other, &[]))
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq<[T; N]> for DequeView<'_, T> {
+    fn eq(&self, other: &[T; N]) -> bool {
+        *self == other[..]
+    }
+}
+
+/// A mutable view of part of a deque's contents, which may span the wrap point.
+#[derive(Debug)]
+pub struct DequeViewMut<'a, T> {
+    front: &'a mut [T],
+    back: &'a mut [T],
+}
+
+impl<'a, T> DequeViewMut<'a, T> {
+    pub(crate) fn new((front, back): (&'a mut [T], &'a mut [T])) -> Self {
+        Self { front, back }
+    }
+
+    pub fn len(&self) -> usize {
+        self.front.len() + self.back.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.as_view().get(index)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        match index.checked_sub(self.front.len()) {
+            None => self.front.get_mut(index),
+            Some(i) => self.back.get_mut(i),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.front.iter().chain(self.back.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.front.iter_mut().chain(self.back.iter_mut())
+    }
+
+    pub fn as_view(&self) -> DequeView<'_, T> {
+        DequeView::new((&*self.front, &*self.back))
+    }
+
+    pub fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        (&mut *self.front, &mut *self.back)
+    }
+
+    /// Converts the view into its two slices, keeping the original borrow's lifetime.
+    pub fn into_slices(self) -> (&'a mut [T], &'a mut [T]) {
+        (self.front, self.back)
+    }
+
+    /// Overwrites the view's elements with `src`. Panics if the lengths differ.
+    pub fn copy_from_slice(&mut self, src: &[T])
+    where
+        T: Copy,
+    {
+        if src.len() != self.len() {
+            panic!("Source length does not match view length");
+        }
+        let (a, b) = src.split_at(self.front.len());
+        self.front.copy_from_slice(a);
+        self.back.copy_from_slice(b);
+    }
+
+    /// Divides the view into the first `index` elements and the rest. Panics if
+    /// `index > len()`.
+    pub fn split_at(self, index: usize) -> (Self, Self) {
+        if index > self.len() {
+            panic!("Split index out of bounds");
+        }
+        match index.checked_sub(self.front.len()) {
+            None => {
+                let (a, b) = self.front.split_at_mut(index);
+                (Self::new((a, &mut [])), Self::new((b, self.back)))
+            }
+            Some(i) => {
+                let (a, b) = self.back.split_at_mut(i);
+                (Self::new((self.front, a)), Self::new((b, &mut [])))
+            }
+        }
+    }
+}
+
+/// Compares two sequences, each given as a pair of slices, without copying.
+pub(crate) fn segments_eq<T: PartialEq>(a: (&[T], &[T]), b: (&[T], &[T])) -> bool {
+    let ((a_front, a_back), (b_front, b_back)) = (a, b);
+    if a_front.len() + a_back.len() != b_front.len() + b_back.len() {
+        return false;
+    }
+    if a_front.len() <= b_front.len() {
+        let (b_mid, b_rest) = b_front.split_at(a_front.len());
+        let (a_mid, a_rest) = a_back.split_at(b_rest.len());
+        a_front == b_mid && a_mid == b_rest && a_rest == b_back
+    } else {
+        segments_eq(b, a)
+    }
+}
+
+impl<T: Copy + Clone + Default, const MAX_STORED: usize> BareMetalDeque<T, MAX_STORED> {
+    /// Divides the contents into views of the first `index` elements and the rest, without
+    /// copying. Panics if `index > len()`.
+    pub fn split_at(&self, index: usize) -> (DequeView<'_, T>, DequeView<'_, T>) {
+        DequeView::new(self.as_slices()).split_at(index)
+    }
+
+    /// Returns a view of the elements in `range`. Panics if the range is out of bounds.
+    pub fn view<R: RangeBounds<usize>>(&self, range: R) -> DequeView<'_, T> {
+        let (start, end) = self.range_bounds(range);
+        DequeView::new(self.as_slices()).split_at(end).0.split_at(start).1
+    }
+
+    /// Returns a mutable view of the elements in `range`. Panics if the range is out of
+    /// bounds.
+    pub fn view_mut<R: RangeBounds<usize>>(&mut self, range: R) -> DequeViewMut<'_, T> {
+        let (start, end) = self.range_bounds(range);
+        DequeViewMut::new(self.as_mut_slices()).split_at(end).0.split_at(start).1
+    }
+
+    pub(crate) fn range_bounds<R: RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+        let start = match range.start_bound() {
+            Bound::Included(&s) => s,
+            Bound::Excluded(&s) => s + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&e) => e + 1,
+            Bound::Excluded(&e) => e,
+            Bound::Unbounded => self.len(),
+        };
+        if start > end || end > self.len() {
+            panic!("Range out of bounds");
+        }
+        (start, end)
+    }
+}
+
+/// Indexing by range returns a plain slice, so it panics if the range spans the wrap
+/// point. Use `view()` for ranges that may.
+macro_rules! impl_range_index {
+    ($($range:ty),*) => {
+        $(
+            impl<T: Copy + Clone + Default, const MAX_STORED: usize> Index<$range> for BareMetalDeque<T, MAX_STORED> {
+                type Output = [T];
+
+                fn index(&self, range: $range) -> &[T] {
+                    match self.view(range).as_slices() {
+                        (front, []) => front,
+                        ([], back) => back,
+                        _ => panic!("Range spans the wrap point"),
+                    }
+                }
+            }
+
+            impl<T: Copy + Clone + Default, const MAX_STORED: usize> IndexMut<$range> for BareMetalDeque<T, MAX_STORED> {
+                fn index_mut(&mut self, range: $range) -> &mut [T] {
+                    match self.view_mut(range).into_slices() {
+                        (front, []) => front,
+                        ([], back) => back,
+                        _ => panic!("Range spans the wrap point"),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_range_index!(Range<usize>, RangeFrom<usize>, RangeTo<usize>, RangeInclusive<usize>, RangeToInclusive<usize>, RangeFull);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrapped_split_test() {
+        let mut q = BareMetalDeque::<u8, 5>::new();
+        q.extend_from_slice(&[0, 0, 1, 2, 3]);
+        q.discard(2);
+        q.extend_from_slice(&[4, 5]);
+        for i in 0..=5 {
+            let (left, right) = q.split_at(i);
+            assert_eq!(left.len(), i);
+            assert!(left.iter().chain(right.iter()).eq(q.iter()));
+            assert_eq!(right.get(0), q.get(i));
+        }
+        let (left, right) = q.split_at(4);
+        assert_eq!(left.as_slices(), (&[1, 2, 3][..], &[4][..]));
+        assert_eq!(right.as_slices(), (&[5][..], &[][..]));
+        assert!(right.split_at(1).1.is_empty());
+    }
+
+    #[test]
+    fn range_view_test() {
+        let mut q = BareMetalDeque::<i32, 6>::new();
+        q.extend_from_slice(&[0, 0, 0, 0, 1, 2]);
+        q.discard(4);
+        q.extend_from_slice(&[3, 4, 5]);
+
+        assert_eq!(q.view(1..4), [2, 3, 4]);
+        assert_eq!(q.view(1..=3), q.view(1..4));
+        assert_ne!(q.view(..2), q.view(1..3));
+        assert!(q.view(5..).is_empty());
+        let mut dst = [0; 5];
+        assert_eq!(q.view(..).copy_to_slice(&mut dst), 5);
+        assert_eq!(dst, [1, 2, 3, 4, 5]);
+
+        q.view_mut(1..3).copy_from_slice(&[20, 30]);
+        *q.view_mut(3..).get_mut(1).unwrap() = 50;
+        assert_eq!(q.view(..), [1, 20, 30, 4, 50]);
+
+        assert_eq!(&q[..2], &[1, 20]);
+        assert_eq!(&q[2..], &[30, 4, 50]);
+        q[3..5].fill(0);
+        assert_eq!(q.view(..), [1, 20, 30, 0, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Range spans the wrap point")]
+    fn wrapped_range_index_test() {
+        let mut q = BareMetalDeque::<i32, 3>::new();
+        q.extend_from_slice(&[0, 1, 2]);
+        q.discard(1);
+        q.push_back(3);
+        let _ = &q[1..3];
+    }
+}
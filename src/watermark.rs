@@ -0,0 +1,82 @@
+//! High/low fill-level thresholds with hysteresis, for flow control such as asserting RTS
+//! or sending XOFF when a receive ring fills up, and releasing it once the ring drains.
+//!
+//! ```
+//! use bare_metal_deque::{BareMetalDeque, Watermark, WatermarkEvent};
+//!
+//! let mut rx = BareMetalDeque::<u8, 8>::new();
+//! let mut flow = Watermark::new(2, 6);
+//! rx.extend_from_slice(b"hello!");
+//! assert_eq!(rx.poll_watermark(&mut flow), Some(WatermarkEvent::High)); // send XOFF
+//! assert_eq!(rx.poll_watermark(&mut flow), None);
+//! rx.pop_batch(4).for_each(drop);
+//! assert_eq!(rx.poll_watermark(&mut flow), Some(WatermarkEvent::Low)); // send XON
+//! ```
+
+use crate::BareMetalDeque;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WatermarkEvent {
+    /// The fill level rose to the high watermark.
+    High,
+    /// After a `High` event, the fill level fell to the low watermark.
+    Low,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Watermark {
+    low: usize,
+    high: usize,
+    above: bool,
+}
+
+impl Watermark {
+    /// Creates thresholds that report `High` when the length reaches `high` and `Low` when
+    /// it then falls to `low`. Panics if `low >= high`.
+    pub const fn new(low: usize, high: usize) -> Self {
+        if low >= high {
+            panic!("Low watermark must be below the high watermark");
+        }
+        Self { low, high, above: false }
+    }
+
+    /// Whether the last poll saw the high watermark without a later `Low` event, i.e.
+    /// whether flow control is currently asserted.
+    pub fn is_high(&self) -> bool {
+        self.above
+    }
+
+    /// Reports a crossing, if `len` has crossed a threshold since the last crossing.
+    pub fn poll(&mut self, len: usize) -> Option<WatermarkEvent> {
+        if !self.above && len >= self.high {
+            self.above = true;
+            Some(WatermarkEvent::High)
+        } else if self.above && len <= self.low {
+            self.above = false;
+            Some(WatermarkEvent::Low)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: Copy + Default, const MAX_STORED: usize> BareMetalDeque<T, MAX_STORED> {
+    /// Checks the fill level against `watermark`, reporting each crossing once.
+    pub fn poll_watermark(&self, watermark: &mut Watermark) -> Option<WatermarkEvent> {
+        watermark.poll(self.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hysteresis_test() {
+        let mut w = Watermark::new(1, 3);
+        let events: Vec<_> = [0, 2, 3, 4, 2, 3, 1, 0, 2, 3].into_iter().map(|len| w.poll(len)).collect();
+        use WatermarkEvent::*;
+        assert_eq!(events, vec![None, None, Some(High), None, None, None, Some(Low), None, None, Some(High)]);
+        assert!(w.is_high());
+    }
+}
@@ -0,0 +1,103 @@
+//! Running sum over the last `WINDOW` samples, updated in constant time per sample, for
+//! throughput counters and moving averages.
+//!
+//! The sum is kept in an accumulator type `A`, which defaults to the sample type; pick a
+//! wider one when `WINDOW` samples could overflow it. Integer accumulators are exact.
+//! Floating-point ones gather rounding error from the repeated add and subtract, so call
+//! `recompute()` now and then for long runs.
+//!
+//! ```
+//! use bare_metal_deque::WindowSum;
+//!
+//! // Bytes per second over the last 4 one-second ticks, summed in u32.
+//! let mut throughput = WindowSum::<u16, 4, u32>::new();
+//! for bytes in [60_000, 60_000, 60_000, 60_000] {
+//!     throughput.push(bytes);
+//! }
+//! assert_eq!(throughput.push(1_000), 181_000);
+//! ```
+
+use core::ops::{Add, Sub};
+
+use crate::BareMetalDeque;
+
+#[derive(Copy, Clone, Debug)]
+pub struct WindowSum<T: Default, const WINDOW: usize, A = T> {
+    window: BareMetalDeque<T, WINDOW>,
+    sum: A,
+}
+
+impl<T, const WINDOW: usize, A> Default for WindowSum<T, WINDOW, A>
+where
+    T: Copy + Default,
+    A: Copy + Default + From<T> + Add<Output = A> + Sub<Output = A>,
+{
+    fn default() -> Self {
+        Self { window: BareMetalDeque::new(), sum: A::default() }
+    }
+}
+
+impl<T, const WINDOW: usize, A> WindowSum<T, WINDOW, A>
+where
+    T: Copy + Default,
+    A: Copy + Default + From<T> + Add<Output = A> + Sub<Output = A>,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a sample, dropping the oldest once `WINDOW` are held, and returns the sum of
+    /// the samples now in the window.
+    pub fn push(&mut self, sample: T) -> A {
+        if WINDOW == 0 {
+            return self.sum;
+        }
+        if self.window.is_full()
+            && let Some(oldest) = self.window.pop_front()
+        {
+            self.sum = self.sum - A::from(oldest);
+        }
+        self.window.push_back(sample);
+        self.sum = self.sum + A::from(sample);
+        self.sum
+    }
+
+    pub fn sum(&self) -> A {
+        self.sum
+    }
+
+    /// The samples in the window, oldest first.
+    pub fn window(&self) -> &BareMetalDeque<T, WINDOW> {
+        &self.window
+    }
+
+    /// Recomputes the sum from the samples in the window.
+    pub fn recompute(&mut self) {
+        self.sum = self.window.iter().fold(A::default(), |sum, x| sum + A::from(*x));
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn running_sum_test() {
+        let mut sums = WindowSum::<i8, 3, i32>::new();
+        let results: Vec<i32> = [100, 100, 100, -100, 5].into_iter().map(|x| sums.push(x)).collect();
+        assert_eq!(results, vec![100, 200, 300, 100, 5]);
+        assert_eq!(sums.window().view(..), [100, -100, 5]);
+
+        let mut floats = WindowSum::<f64, 2>::new();
+        floats.push(0.1);
+        floats.push(0.2);
+        floats.push(0.3);
+        floats.recompute();
+        assert_eq!(floats.sum(), 0.2 + 0.3);
+        assert_eq!(WindowSum::<u8, 0>::new().push(7), 0);
+    }
+}